@@ -0,0 +1,85 @@
+//! Finds how few shots clear every level in `assets/levels`, by playing them without a window.
+//!
+//! ```sh
+//! cargo run --release --bin solve_par
+//! cargo run --release --bin solve_par -- --angles 36 --max-shots 3 01_simple
+//! ```
+//!
+//! Flags set how finely shots are swept, see `SolverSettings`. Anything else picks the level
+//! files to solve by part of their path. Exits with an error if any level could not be read or
+//! cleared.
+
+use std::{path::Path, process::ExitCode};
+
+use bow_game::{Solution, SolverSettings};
+
+fn main() -> ExitCode {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+
+    let mut settings = SolverSettings::default();
+    let mut only = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let setting = match arg.as_str() {
+            "--columns" => &mut settings.columns,
+            "--angles" => &mut settings.angles,
+            "--strengths" => &mut settings.strengths,
+            "--max-shots" => &mut settings.max_shots,
+            "--beam" => &mut settings.beam,
+            _ => {
+                only.push(arg);
+                continue;
+            }
+        };
+        let Some(value) = args.next().and_then(|value| value.parse().ok()) else {
+            eprintln!("{arg} needs a number after it");
+            return ExitCode::FAILURE;
+        };
+        *setting = value;
+    }
+
+    let reports = match bow_game::solve_level_files(&assets, &settings, &only) {
+        Ok(reports) => reports,
+        Err(err) => {
+            eprintln!("Could not solve levels: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut broken = 0;
+    for (path, solution) in &reports {
+        match solution {
+            Ok((par, solution @ Solution::Cleared(shots))) => {
+                println!(
+                    "{path}: cleared in {} shots (par {par})",
+                    solution.strokes()
+                );
+                for (index, shot) in shots.iter().enumerate() {
+                    println!("    {}. {shot}", index + 1);
+                }
+            }
+            Ok((par, solution @ Solution::Stuck { shots, remaining })) => {
+                broken += 1;
+                println!(
+                    "{path}: not cleared, {remaining} spheres left after {} shots (par {par})",
+                    solution.strokes()
+                );
+                for (index, shot) in shots.iter().enumerate() {
+                    println!("    {}. {shot}", index + 1);
+                }
+            }
+            Err(err) => {
+                broken += 1;
+                println!("{path}: {err}");
+            }
+        }
+    }
+
+    if broken == 0 {
+        println!("All {} levels can be cleared", reports.len());
+        ExitCode::SUCCESS
+    } else {
+        println!("{broken} of {} levels could not be cleared", reports.len());
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,49 @@
+//! Checks every level in `assets/levels` without opening a window.
+//!
+//! ```sh
+//! cargo run --bin validate_levels
+//! ```
+//!
+//! Exits with an error if any level could not be read or has a problem.
+
+use std::{path::Path, process::ExitCode};
+
+fn main() -> ExitCode {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+
+    let reports = match bow_game::validate_level_files(&assets) {
+        Ok(reports) => reports,
+        Err(err) => {
+            eprintln!("Could not read the course list: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut broken = 0;
+    for (path, problems) in &reports {
+        match problems {
+            Ok(problems) if problems.is_empty() => {
+                println!("{path}: ok");
+            }
+            Ok(problems) => {
+                broken += 1;
+                println!("{path}:");
+                for problem in problems {
+                    println!("    {problem}");
+                }
+            }
+            Err(err) => {
+                broken += 1;
+                println!("{path}: {err}");
+            }
+        }
+    }
+
+    if broken == 0 {
+        println!("All {} levels are fine", reports.len());
+        ExitCode::SUCCESS
+    } else {
+        println!("{broken} of {} levels have problems", reports.len());
+        ExitCode::FAILURE
+    }
+}
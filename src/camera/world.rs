@@ -11,6 +11,9 @@ use crate::{
     gameplay::GAMEPLAY_CAMERA_OFFSET,
 };
 
+/// The vertical field of view of the world camera
+pub const WORLD_CAMERA_FOV: f32 = 45.0_f32.to_radians();
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Startup, spawn_world_camera);
 }
@@ -34,7 +37,7 @@ fn spawn_world_camera(mut commands: Commands) {
         Tonemapping::TonyMcMapface, // 2. Using a tonemapper that desaturates to white is recommended
         Transform::from_xyz(0., 0., GAMEPLAY_CAMERA_OFFSET).looking_at(Vec3::ZERO, Vec3::Y),
         Projection::from(PerspectiveProjection {
-            fov: 45.0_f32.to_radians(),
+            fov: WORLD_CAMERA_FOV,
             ..default()
         }),
         RenderLayers::from(RenderLayer::DEFAULT | RenderLayer::PARTICLES | RenderLayer::GIZMO3),
@@ -2,7 +2,10 @@
 
 use bevy::{color::palettes::tailwind::SKY_900, ecs::spawn::SpawnIter, prelude::*, ui::Val::*};
 
-use crate::{Screen, theme::widgets};
+use crate::{
+    Screen,
+    theme::{interaction::OnPress, widgets},
+};
 
 #[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default, Reflect)]
 #[source(Screen = Screen::Title)]
@@ -100,9 +103,6 @@ fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
     )
 }
 
-fn enter_title_screen(
-    _: Trigger<Pointer<Click>>,
-    mut next_screen: ResMut<NextState<CreditsState>>,
-) {
+fn enter_title_screen(_: Trigger<OnPress>, mut next_screen: ResMut<NextState<CreditsState>>) {
     next_screen.set(CreditsState::None);
 }
@@ -1,6 +1,7 @@
 //! Toggles for the different debug UIs that our plugins provide.
 
 use crate::camera::RenderLayer;
+use crate::gameplay::sphere::ChainReactionGizmos;
 use crate::settings::Settings;
 use crate::{AppSystems, theme::widgets};
 use avian3d::prelude::*;
@@ -62,6 +63,7 @@ pub(super) fn plugin(app: &mut App) {
             toggle_debug_ui.run_if(toggled_state(DebugState::Ui)),
             toggle_lighting_debug_ui.run_if(toggled_state(DebugState::Lighting)),
             toggle_physics_debug_ui.run_if(toggled_state(DebugState::Physics)),
+            toggle_chain_reaction_debug_ui.run_if(toggled_state(DebugState::ChainReactions)),
         )
             .chain()
             .in_set(AppSystems::ChangeUi),
@@ -106,6 +108,7 @@ fn update_debug_ui_text(
         DebugState::Lighting => "Lighting",
         DebugState::Physics => "Physics",
         DebugState::Landmass => "Landmass",
+        DebugState::ChainReactions => "Chain Reactions",
     }
     .to_string();
 }
@@ -128,6 +131,11 @@ fn toggle_lighting_debug_ui(mut config_store: ResMut<GizmoConfigStore>) {
     config.enabled = !config.enabled;
 }
 
+fn toggle_chain_reaction_debug_ui(mut config_store: ResMut<GizmoConfigStore>) {
+    let config = config_store.config_mut::<ChainReactionGizmos>().0;
+    config.enabled = !config.enabled;
+}
+
 fn toggle_fps_overlay(mut config: ResMut<FpsOverlayConfig>) {
     config.enabled = !config.enabled;
 }
@@ -140,6 +148,7 @@ enum DebugState {
     Lighting,
     Physics,
     Landmass,
+    ChainReactions,
 }
 
 impl DebugState {
@@ -149,7 +158,8 @@ impl DebugState {
             Self::Ui => Self::Lighting,
             Self::Lighting => Self::Physics,
             Self::Physics => Self::Landmass,
-            Self::Landmass => Self::None,
+            Self::Landmass => Self::ChainReactions,
+            Self::ChainReactions => Self::None,
         }
     }
 }
@@ -1,15 +1,19 @@
-use std::{f32::consts::FRAC_PI_2, path::Path, time::Duration};
+use std::{f32::consts::FRAC_PI_2, fmt, path::Path, time::Duration};
 
 use avian3d::prelude::*;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     asset_tracking::LoadResource,
     gameplay::{
         GameSet,
         bow::BowArrow,
-        level::Walls,
-        sphere::{FromAbsorberMultiply, HitByExplosion, ShouldMultiply},
+        level::{HitWall, Level, LevelState, Levels, MovingWall, RebuildLevel, WallKind, Walls},
+        sphere::{
+            Blast, FromAbsorberMultiply, HitByExplosion, Portal, SPLIT_ANGLE, ShouldMultiply,
+            Sphere, detonate,
+        },
     },
     third_party::avian3d::GameLayer,
     world::GAME_PLANE,
@@ -20,9 +24,11 @@ use super::ArrowSet;
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<NockedOn>()
         .register_type::<Arrow>()
+        .register_type::<ArrowKind>()
         .register_type::<Canceled>()
         .register_type::<MaxFlightTime>()
         .register_type::<ArrowAssets>()
+        .init_resource::<ArrowPhysics>()
         .load_resource::<ArrowAssets>();
 
     app.add_systems(
@@ -31,8 +37,18 @@ pub(super) fn plugin(app: &mut App) {
     )
     .add_systems(Update, tick_flight_time.in_set(GameSet::TickTimers))
     .add_systems(PostUpdate, (reset_flight_time, despawn_arrows).chain())
+    .add_systems(OnEnter(LevelState::Playing), use_level_physics)
+    .add_observer(
+        |_: Trigger<RebuildLevel>,
+         physics: ResMut<ArrowPhysics>,
+         levels: Res<Levels>,
+         level: Res<Level>| {
+            use_level_physics(physics, levels, level);
+        },
+    )
     .add_observer(spawn_arrow)
-    .add_observer(add_arrow_colliders);
+    .add_observer(add_arrow_colliders)
+    .add_observer(set_flight_time);
 }
 
 #[derive(Resource, Asset, Reflect, Clone)]
@@ -56,16 +72,145 @@ impl FromWorld for ArrowAssets {
 }
 
 #[derive(Event)]
-pub struct ReadyArrow(Entity);
+pub struct ReadyArrow {
+    bow: Entity,
+    kind: ArrowKind,
+}
 
 impl ReadyArrow {
     pub fn for_bow(bow: Entity) -> Self {
-        Self(bow)
+        Self {
+            bow,
+            kind: ArrowKind::Normal,
+        }
+    }
+    /// Nocks a `kind` arrow instead of a normal one
+    pub fn with_kind(mut self, kind: ArrowKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+/// What an arrow does besides breaking spheres.
+///
+/// Normal arrows never run out, the rest are handed out by the level's quiver.
+#[derive(
+    Component,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Reflect,
+    Serialize,
+    Deserialize,
+)]
+#[reflect(Component)]
+pub enum ArrowKind {
+    #[default]
+    Normal,
+    /// Flies through every sphere, marking the ones that have to be marked instead of bouncing
+    /// off of them
+    Piercing,
+    /// Knocks spheres away instead of shattering them. Knocked spheres break whatever they run
+    /// into, and themselves with it.
+    Heavy,
+    /// Forks into three as it leaves the bow
+    Split,
+    /// Blows up on the first sphere or wall it touches
+    Explosive,
+}
+
+impl fmt::Display for ArrowKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Normal => "Normal",
+            Self::Piercing => "Piercing",
+            Self::Heavy => "Heavy",
+            Self::Split => "Split",
+            Self::Explosive => "Explosive",
+        };
+        f.write_str(name)
     }
 }
 
 const ARROW_RADIUS: f32 = 0.1;
 const ARROW_LEN: f32 = 3.5;
+pub const ARROW_SCALE: f32 = 2.;
+/// How dense the collider of a heavy arrow is, normal arrows are 10
+const HEAVY_DENSITY: f32 = 40.;
+/// How far the blast of an explosive arrow reaches
+const EXPLOSIVE_TIP_RADIUS: f32 = 5.;
+
+/// How arrows fly in the level being played.
+///
+/// Courses can change it for all of their levels, and levels for themselves. Anything missing
+/// from a course or level file is filled in like [`ArrowPhysics::default`], not from the course.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArrowPhysics {
+    /// how many times an arrow bounces off of walls before it stops, bouncy walls don't count
+    pub max_bounces: u8,
+    /// how much of its speed an arrow keeps when it bounces, bouncy walls keep all of it
+    pub energy_retention: f32,
+    /// how fast an arrow flies off of a fully drawn bow
+    pub max_speed: f32,
+    /// arrows slower than this are canceled instead of fired
+    pub min_speed: f32,
+    /// seconds an arrow flies without bouncing before it is gone
+    pub flight_time: f32,
+}
+
+impl Default for ArrowPhysics {
+    fn default() -> Self {
+        Self {
+            max_bounces: 2,
+            energy_retention: 0.8,
+            max_speed: 60.,
+            min_speed: 15.,
+            flight_time: 10.,
+        }
+    }
+}
+
+impl ArrowPhysics {
+    /// How fast an arrow flies off of a bow pulled back by `strength`, from 0 to 1
+    pub fn arrow_speed(&self, strength: f32) -> f32 {
+        strength.clamp(0., 1.).powi(2) * self.max_speed
+    }
+
+    /// The velocity of an arrow flying at `velocity` after it bounces off of a `kind` wall.
+    ///
+    /// Both are as seen from the wall, and `normal` points from the wall to the arrow.
+    pub fn bounce(&self, velocity: Vec3, normal: Vec3, kind: WallKind) -> Vec3 {
+        let dot_product = velocity.dot(normal);
+
+        // Reflect the arrow's velocity around the wall normal using the formula:
+        // reflected_velocity = velocity - 2 * (velocity · normal) * normal
+        // An arrow already moving away from the wall was caught up to, and only gets pushed along.
+        let reflected_velocity = if dot_product < 0. {
+            velocity - 2.0 * dot_product * normal
+        } else {
+            velocity
+        };
+
+        // Apply energy damping to make bounces more realistic. Bouncy walls keep the arrow's speed.
+        let retention = if kind == WallKind::Bouncy {
+            1.
+        } else {
+            self.energy_retention
+        };
+        reflected_velocity * retention
+    }
+}
+
+fn use_level_physics(mut physics: ResMut<ArrowPhysics>, levels: Res<Levels>, level: Res<Level>) {
+    *physics = levels.physics(level.0);
+}
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
@@ -77,8 +222,9 @@ pub struct NockedOn(Entity);
 #[require(RigidBody = RigidBody::Dynamic)]
 #[require(GravityScale = GravityScale(0.))]
 #[require(LockedAxes = LockedAxes::ROTATION_LOCKED.lock_translation_z())]
-#[require(Transform = Transform::from_xyz(0., 0., -50.).with_scale(Vec3::splat(2.)))]
+#[require(Transform = Transform::from_xyz(0., 0., -50.).with_scale(Vec3::splat(ARROW_SCALE)))]
 #[require(MaxFlightTime)]
+#[require(ArrowKind)]
 pub struct Arrow {
     pub bounces: u8,
 }
@@ -89,37 +235,61 @@ fn spawn_arrow(trigger: Trigger<ReadyArrow>, mut commands: Commands, assets: Res
         .spawn((
             Name::new("Arrow"),
             Arrow::default(),
+            trigger.event().kind,
             SceneRoot(assets.glowing.clone()),
-            NockedOn(trigger.event().0),
+            NockedOn(trigger.event().bow),
         ))
         .observe(fire_arrow)
         .observe(cancel_arrow);
 }
 
 // we will always overwrite children of arrow with 2 colliders
-fn add_arrow_colliders(trigger: Trigger<OnAdd, Arrow>, mut commands: Commands) {
+fn add_arrow_colliders(
+    trigger: Trigger<OnAdd, Arrow>,
+    mut commands: Commands,
+    kinds: Query<&ArrowKind>,
+) {
+    let kind = kinds.get(trigger.target()).copied().unwrap_or_default();
     let collider = Collider::capsule(ARROW_RADIUS, ARROW_LEN);
 
-    let sensor = commands
-        .spawn((
-            collider.clone(),
-            Sensor,
-            CollisionLayers::new(
-                GameLayer::ArrowSensor,
-                [GameLayer::ArrowSensor, GameLayer::Sphere, GameLayer::Walls],
-            ),
-            CollisionEventsEnabled,
-        ))
-        .observe(wall_collision_flip)
-        .id();
+    let mut sensor = commands.spawn((
+        collider.clone(),
+        Sensor,
+        CollisionLayers::new(
+            GameLayer::ArrowSensor,
+            [GameLayer::ArrowSensor, GameLayer::Sphere, GameLayer::Walls],
+        ),
+        CollisionEventsEnabled,
+    ));
+    sensor.observe(wall_collision_flip);
+    if kind == ArrowKind::Explosive {
+        sensor.observe(detonate_on_contact);
+    }
+    let sensor = sensor.id();
 
-    let arrow_collider = commands
-        .spawn((
-            collider,
-            ColliderDensity(10.),
-            CollisionLayers::new(GameLayer::Arrow, [GameLayer::Sphere, GameLayer::Backdrop]),
-        ))
-        .id();
+    let density = if kind == ArrowKind::Heavy {
+        HEAVY_DENSITY
+    } else {
+        10.
+    };
+    let mut arrow_collider = commands.spawn((
+        collider,
+        ColliderDensity(density),
+        CollisionLayers::new(GameLayer::Arrow, [GameLayer::Sphere, GameLayer::Backdrop]),
+    ));
+    match kind {
+        // still touches the spheres it would bounce off of, so they get marked
+        ArrowKind::Piercing => {
+            arrow_collider.insert(Sensor);
+        }
+        ArrowKind::Explosive => {
+            arrow_collider
+                .insert(CollisionEventsEnabled)
+                .observe(detonate_on_contact);
+        }
+        ArrowKind::Normal | ArrowKind::Heavy | ArrowKind::Split => {}
+    }
+    let arrow_collider = arrow_collider.id();
 
     commands
         .entity(trigger.target())
@@ -135,15 +305,21 @@ fn wall_collision_flip(
         &mut Rotation,
         &mut LinearVelocity,
     )>,
-    walls: Query<(), With<Walls>>,
+    walls: Query<
+        Option<(&LinearVelocity, &AngularVelocity, &Position)>,
+        (Or<(With<Walls>, With<MovingWall>)>, Without<Arrow>),
+    >,
+    kinds: Query<(&WallKind, &GlobalTransform)>,
     colliders: Query<&ColliderOf>,
     collisions: Collisions,
+    physics: Res<ArrowPhysics>,
+    mut commands: Commands,
 ) {
     let Ok(wall) = colliders.get(trigger.collider) else {
         return;
     };
 
-    if walls.get(wall.body).is_err() {
+    let Ok(wall_motion) = walls.get(wall.body) else {
         return;
     };
     let Ok(arrow) = colliders.get(trigger.target()) else {
@@ -179,27 +355,51 @@ fn wall_collision_flip(
     }
     // If wall is collider1, the normal already points from wall to arrow
 
-    // Check if arrow has bounced too many times
-    const MAX_BOUNCES: u8 = 2;
-    if arrow.bounces >= MAX_BOUNCES {
+    // How fast the wall is moving where the arrow hit it. Zero for walls that stand still.
+    let wall_velocity = wall_motion
+        .map(|(linear, angular, position)| {
+            linear.0 + angular.0.cross(arrow_position.0 - position.0)
+        })
+        .unwrap_or(Vec3::ZERO);
+
+    // Bounce off of the wall as seen from the wall, so moving walls hit arrows back
+    let current_velocity = arrow_velocity.0 - wall_velocity;
+
+    let (kind, wall_rotation) = kinds
+        .get(trigger.collider)
+        .map(|(kind, transform)| (*kind, transform.rotation()))
+        .unwrap_or((WallKind::Plain, Quat::IDENTITY));
+    match kind {
+        WallKind::OneWay { facing } => {
+            // Arrows coming from behind fly through
+            if current_velocity.dot(wall_rotation * facing.extend(0.)) >= 0. {
+                return;
+            }
+        }
+        WallKind::Sticky => {
+            arrow_velocity.0 = Vec3::ZERO;
+            return;
+        }
+        WallKind::Breakable { .. } => {
+            commands.trigger_targets(HitWall, trigger.collider);
+        }
+        WallKind::Plain | WallKind::Bouncy => {}
+    }
+
+    // Check if arrow has bounced too many times. Bouncy walls don't count.
+    if kind != WallKind::Bouncy && arrow.bounces >= physics.max_bounces {
         // Stop the arrow instead of bouncing
         arrow_velocity.0 = Vec3::ZERO;
         return;
     }
 
-    // Reflect the arrow's velocity around the wall normal using the formula:
-    // reflected_velocity = velocity - 2 * (velocity · normal) * normal
-    let current_velocity = arrow_velocity.0;
-    let dot_product = current_velocity.dot(wall_normal);
-    let reflected_velocity = current_velocity - 2.0 * dot_product * wall_normal;
-
-    // Apply energy damping to make bounces more realistic
-    const ENERGY_RETENTION: f32 = 0.8; // Arrow loses 20% of energy per bounce
-    let damped_velocity = reflected_velocity * ENERGY_RETENTION;
+    let damped_velocity = physics.bounce(current_velocity, wall_normal, kind) + wall_velocity;
 
     // Update arrow velocity and bounce count
     arrow_velocity.0 = damped_velocity;
-    arrow.bounces += 1;
+    if kind != WallKind::Bouncy {
+        arrow.bounces += 1;
+    }
 
     // Update arrow rotation to match new direction
     if damped_velocity.length() > 0.001 {
@@ -222,33 +422,43 @@ fn update_unfired_arrow_transform(
         let Ok((bow, pull_strength)) = bow.get(arrow_of.0) else {
             continue;
         };
-        // since the strength is from 0, 1, that scales from 0 to this number
-        const BOW_RIGIDITY: f32 = 5.;
-        /// this is how far to translate the arrow to sit on the bow string
-        const STRING_OFFSET: f32 = -3.;
-        let sv = pull_strength.strength() * BOW_RIGIDITY;
-        let strength_vec = bow.rotation * Vec3::new(sv + STRING_OFFSET, 0., 0.);
-        arrow.translation = bow.translation + strength_vec;
-        let (z, _, _) = bow.rotation.to_euler(EulerRot::ZXY);
-        arrow.rotation = Quat::from_rotation_z(z + FRAC_PI_2);
+        nock(&mut arrow, bow, pull_strength.strength());
     }
 }
 
-/// the arrow will be fired, but will be canceled if this velocity is not reached
-//TODO: set this back to 15ish
-pub const ARROW_VELOCITY_THRESHOLD: f32 = 15.;
+/// Puts an arrow on the string of a bow pulled back by `strength`, from 0 to 1
+pub fn nock(arrow: &mut Transform, bow: &Transform, strength: f32) {
+    // since the strength is from 0, 1, that scales from 0 to this number
+    const BOW_RIGIDITY: f32 = 5.;
+    /// this is how far to translate the arrow to sit on the bow string
+    const STRING_OFFSET: f32 = -3.;
+    let sv = strength * BOW_RIGIDITY;
+    let strength_vec = bow.rotation * Vec3::new(sv + STRING_OFFSET, 0., 0.);
+    arrow.translation = bow.translation + strength_vec;
+    let (z, _, _) = bow.rotation.to_euler(EulerRot::ZXY);
+    arrow.rotation = Quat::from_rotation_z(z + FRAC_PI_2);
+}
 
+/// Lets go of the targeted arrow, which is of the carried kind
 #[derive(Event)]
-pub struct FireArrow;
+pub struct FireArrow(pub ArrowKind);
 
 fn fire_arrow(
     trigger: Trigger<FireArrow>,
     mut commands: Commands,
-    mut arrows: Query<(&Rotation, &mut LinearVelocity, &NockedOn)>,
+    mut arrows: Query<(
+        &Transform,
+        &Rotation,
+        &mut LinearVelocity,
+        &NockedOn,
+        &SceneRoot,
+    )>,
     mut pull_strength: Query<&BowArrow, Without<NockedOn>>,
+    physics: Res<ArrowPhysics>,
 ) {
     info!("fire arrow event");
-    let Ok((rotation, mut lvel, arrow_of)) = arrows.get_mut(trigger.target()) else {
+    let Ok((transform, rotation, mut lvel, arrow_of, scene)) = arrows.get_mut(trigger.target())
+    else {
         //right click and left click were clicked together
         return;
     };
@@ -257,18 +467,65 @@ fn fire_arrow(
         return;
     };
 
-    let arrow_velocity = pull_strength.arrow_velocity();
+    let arrow_velocity = pull_strength.arrow_velocity(&physics);
 
     let velocity = rotation.0 * Vec3::new(0., arrow_velocity, 0.);
     lvel.0 = velocity;
-    let mut arrow_commands = commands.entity(trigger.target());
-    arrow_commands.remove::<NockedOn>();
-    if arrow_velocity >= ARROW_VELOCITY_THRESHOLD {
-        arrow_commands.observe(on_multiply);
-    } else {
+    commands.entity(trigger.target()).remove::<NockedOn>();
+    // the arrow is fired either way, but it is canceled if it isn't fast enough
+    if arrow_velocity < physics.min_speed {
         commands.trigger_targets(CancelArrow, trigger.target());
+        return;
+    }
+    commands.entity(trigger.target()).observe(on_multiply);
+
+    if trigger.event().0 == ArrowKind::Split {
+        // the copies are normal arrows, turned away like the ones out of a multiplier
+        for angle in [SPLIT_ANGLE, -SPLIT_ANGLE] {
+            let turn = Quat::from_rotation_z(angle);
+            spawn_flying_arrow(
+                &mut commands,
+                "Split arrow",
+                transform.with_rotation(rotation.0 * turn),
+                turn * velocity,
+                scene.clone(),
+            );
+        }
+    }
+}
+
+/// Sets off the blast of an explosive arrow when it touches a sphere or a wall
+fn detonate_on_contact(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    arrows: Query<&Position, (With<Arrow>, Without<NockedOn>)>,
+    spheres: Query<(), (With<Sphere>, Without<Portal>)>,
+    walls: Query<(), Or<(With<Walls>, With<MovingWall>)>>,
+    colliders: Query<&ColliderOf>,
+) {
+    let Ok(arrow) = colliders.get(trigger.target()) else {
+        return;
+    };
+    let Ok(other) = colliders.get(trigger.collider) else {
+        return;
+    };
+    if !spheres.contains(other.body) && !walls.contains(other.body) {
+        return;
     }
+    let Ok(position) = arrows.get(arrow.body) else {
+        return;
+    };
+    detonate(
+        &mut commands,
+        position.0,
+        Blast {
+            radius: EXPLOSIVE_TIP_RADIUS,
+            ..default()
+        },
+    );
+    commands.entity(arrow.body).try_despawn();
 }
+
 fn despawn_on_explosion(trigger: Trigger<HitByExplosion>, mut commands: Commands) {
     commands.entity(trigger.target()).try_despawn();
 }
@@ -297,20 +554,38 @@ fn on_multiply(
             .with_rotation(rotation)
             .with_scale(arrow_trn.scale);
 
-        let mut commands = commands.spawn((
-            Name::new("Cloned arrow"),
-            Arrow::default(),
+        let mut commands = spawn_flying_arrow(
+            &mut commands,
+            "Cloned arrow",
             transform,
-            LinearVelocity(velocity),
+            velocity,
             scene_root.clone(),
-        ));
-        commands.observe(on_multiply).observe(despawn_on_explosion);
+        );
         if event.is_from_absorber {
             commands.insert(FromAbsorberMultiply::default());
         }
     }
 }
 
+/// Spawns an arrow that is already flying at `velocity`, the way fired arrows fly
+pub fn spawn_flying_arrow<'a>(
+    commands: &'a mut Commands,
+    name: &'static str,
+    transform: Transform,
+    velocity: Vec3,
+    scene: SceneRoot,
+) -> EntityCommands<'a> {
+    let mut arrow = commands.spawn((
+        Name::new(name),
+        Arrow::default(),
+        transform,
+        LinearVelocity(velocity),
+        scene,
+    ));
+    arrow.observe(on_multiply).observe(despawn_on_explosion);
+    arrow
+}
+
 #[derive(Event)]
 pub struct CancelArrow;
 
@@ -343,7 +618,20 @@ impl MaxFlightTime {
 
 impl Default for MaxFlightTime {
     fn default() -> Self {
-        Self(Timer::new(Duration::from_secs(10), TimerMode::Once))
+        Self::new(Duration::from_secs_f32(ArrowPhysics::default().flight_time))
+    }
+}
+
+fn set_flight_time(
+    trigger: Trigger<OnAdd, Arrow>,
+    physics: Res<ArrowPhysics>,
+    mut timers: Query<&mut MaxFlightTime>,
+) {
+    if let Ok(mut timer) = timers.get_mut(trigger.target()) {
+        // flight times too long to count, like `inf`, never run out
+        let flight_time =
+            Duration::try_from_secs_f32(physics.flight_time.max(0.)).unwrap_or(Duration::MAX);
+        *timer = MaxFlightTime::new(flight_time);
     }
 }
 
@@ -0,0 +1,184 @@
+//! Traces the shot while the bow is drawn.
+//!
+//! The preview follows the arrow through its wall bounces and the split at the first multiplier
+//! it would hit, and forks split arrows as they leave the bow. It is an assist, so it only shows
+//! up on practice courses and while play-testing in the editor, and only when it is turned on in
+//! the settings.
+
+use avian3d::prelude::*;
+use bevy::{
+    color::palettes::css::{ORANGE, WHITE},
+    prelude::*,
+};
+
+use crate::{
+    gameplay::{
+        GameSet,
+        arrow::{ArrowKind, ArrowPhysics, NockedOn},
+        bow::BowArrow,
+        level::{Course, Courses, CurrentCourse, EditorSession, LevelState, WallKind},
+        sphere::{Multiplier, SPLIT_ANGLE},
+    },
+    settings::Settings,
+    third_party::avian3d::GameLayer,
+};
+
+/// How many seconds of the arrow's flight the preview shows
+const PREVIEW_TIME: f32 = 1.5;
+/// Most straight pieces a single path is traced through, so arrows caught between bouncy walls
+/// don't go on forever
+const MAX_SEGMENTS: usize = 12;
+/// How far off of a wall a traced path starts again
+const SEPARATION: f32 = 0.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        draw_aim_preview
+            .in_set(GameSet::Update)
+            .run_if(in_state(LevelState::Playing).and(aim_preview_allowed)),
+    );
+}
+
+fn aim_preview_allowed(
+    settings: Res<Settings>,
+    editor: Option<Res<EditorSession>>,
+    courses: Res<Courses>,
+    current: Res<CurrentCourse>,
+) -> bool {
+    settings.aim_preview
+        && (editor.is_some() || courses.get(current.0).is_some_and(Course::is_practice))
+}
+
+/// Part of the shot that is still to be traced
+struct Trace {
+    from: Vec3,
+    velocity: Vec3,
+    bounces: u8,
+    /// seconds of flight left to trace
+    time: f32,
+    /// whether it can still split at a multiplier
+    can_split: bool,
+}
+
+fn draw_aim_preview(
+    bows: Query<&BowArrow>,
+    arrows: Query<(&Transform, &ArrowKind), With<NockedOn>>,
+    walls: Query<(&WallKind, &GlobalTransform)>,
+    multipliers: Query<(), With<Multiplier>>,
+    colliders: Query<&ColliderOf>,
+    spatial_query: SpatialQuery,
+    physics: Res<ArrowPhysics>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(bow) = bows.single() else {
+        return;
+    };
+    let speed = bow.arrow_velocity(&physics);
+    // too weak a shot is canceled instead of fired
+    if speed < physics.min_speed {
+        return;
+    }
+    let Ok((arrow, kind)) = arrows.get(bow.arrow()) else {
+        return;
+    };
+
+    let wall_filter = SpatialQueryFilter::from_mask(GameLayer::Walls);
+    let sphere_filter = SpatialQueryFilter::from_mask(GameLayer::Sphere);
+    let is_multiplier = |entity: Entity| {
+        colliders
+            .get(entity)
+            .is_ok_and(|collider| multipliers.contains(collider.body))
+    };
+
+    // Arrow points along +Y axis in its local space
+    let velocity = arrow.rotation * Vec3::new(0., speed, 0.);
+    let shot = |angle: f32| Trace {
+        from: arrow.translation,
+        velocity: Quat::from_rotation_z(angle) * velocity,
+        bounces: 0,
+        // levels can keep their arrows in the air for less than that
+        time: PREVIEW_TIME.min(physics.flight_time),
+        can_split: true,
+    };
+    let mut traces = vec![(shot(0.), Color::from(WHITE))];
+    if *kind == ArrowKind::Split {
+        traces.push((shot(SPLIT_ANGLE), Color::from(ORANGE)));
+        traces.push((shot(-SPLIT_ANGLE), Color::from(ORANGE)));
+    }
+    while let Some((mut trace, color)) = traces.pop() {
+        let mut points = vec![trace.from];
+        for _ in 0..MAX_SEGMENTS {
+            let Ok(direction) = Dir3::new(trace.velocity) else {
+                break;
+            };
+            let speed = trace.velocity.length();
+            let reach = speed * trace.time;
+            let wall_hit = spatial_query.cast_ray(trace.from, direction, reach, true, &wall_filter);
+
+            if trace.can_split {
+                let wall_distance = wall_hit.map_or(reach, |hit| hit.distance);
+                let split = spatial_query.cast_ray_predicate(
+                    trace.from,
+                    direction,
+                    wall_distance,
+                    true,
+                    &sphere_filter,
+                    &is_multiplier,
+                );
+                if let Some(split) = split {
+                    let at = trace.from + direction * split.distance;
+                    points.push(at);
+                    trace.time -= split.distance / speed;
+                    // the copies start out like freshly fired arrows, and don't split again
+                    for angle in [SPLIT_ANGLE, -SPLIT_ANGLE] {
+                        let copy = Trace {
+                            from: at,
+                            velocity: Quat::from_rotation_z(angle) * trace.velocity,
+                            bounces: 0,
+                            time: trace.time,
+                            can_split: false,
+                        };
+                        traces.push((copy, Color::from(ORANGE)));
+                    }
+                    trace.from = at;
+                    trace.can_split = false;
+                    continue;
+                }
+            }
+
+            let Some(hit) = wall_hit else {
+                points.push(trace.from + direction * reach);
+                break;
+            };
+            let at = trace.from + direction * hit.distance;
+            points.push(at);
+            trace.time -= hit.distance / speed;
+
+            let (kind, wall_rotation) = walls
+                .get(hit.entity)
+                .map(|(kind, transform)| (*kind, transform.rotation()))
+                .unwrap_or((WallKind::Plain, Quat::IDENTITY));
+            match kind {
+                WallKind::OneWay { facing } => {
+                    // Arrows coming from behind fly through
+                    if trace.velocity.dot(wall_rotation * facing.extend(0.)) >= 0. {
+                        trace.from = at + direction * SEPARATION;
+                        continue;
+                    }
+                }
+                WallKind::Sticky => break,
+                WallKind::Plain | WallKind::Bouncy | WallKind::Breakable { .. } => {}
+            }
+            if kind != WallKind::Bouncy {
+                if trace.bounces >= physics.max_bounces {
+                    break;
+                }
+                trace.bounces += 1;
+            }
+            trace.velocity = physics.bounce(trace.velocity, hit.normal, kind);
+            trace.from = at + hit.normal * SEPARATION;
+        }
+        gizmos.linestrip(points, color.with_alpha(0.5));
+    }
+}
@@ -4,11 +4,16 @@ use bevy::prelude::*;
 
 use crate::{
     asset_tracking::LoadResource,
-    gameplay::{ArrowSet, arrow::NockedOn, cursor::CursorPosition},
+    gameplay::{
+        ArrowSet,
+        arrow::{ArrowPhysics, NockedOn},
+        cursor::CursorPosition,
+    },
     rand::random_range,
     settings::Settings,
 };
 
+mod aim;
 mod animation;
 mod timefreeze;
 
@@ -18,7 +23,7 @@ pub use primary::*;
 use super::arrow::{FireArrow, ReadyArrow};
 
 /// how far from the bow the player must draw bow
-const MAX_RADIUS: f32 = 10.;
+pub(super) const MAX_RADIUS: f32 = 10.;
 const EPS: f32 = 1e-3;
 
 pub(super) fn plugin(app: &mut App) {
@@ -27,7 +32,12 @@ pub(super) fn plugin(app: &mut App) {
         .register_type::<BowAssets>()
         .load_resource::<BowAssets>();
 
-    app.add_plugins((timefreeze::plugin, animation::plugin, primary::plugin));
+    app.add_plugins((
+        timefreeze::plugin,
+        animation::plugin,
+        primary::plugin,
+        aim::plugin,
+    ));
 
     app.add_systems(Update, update_pull_strength.in_set(ArrowSet::ProcessInput))
         .add_systems(Update, update_pull_rotation.in_set(ArrowSet::UpdateBow))
@@ -61,9 +71,6 @@ impl FromWorld for BowAssets {
     }
 }
 
-/// the max linear velocity speed of the arrow
-const STRENGTH_MULT: f32 = 60.;
-
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 #[relationship_target(relationship = NockedOn)]
@@ -88,8 +95,8 @@ impl BowArrow {
 
     /// based on the current strength, this returns the
     /// velocity of the arrow
-    pub fn arrow_velocity(&self) -> f32 {
-        self.strength().powi(2) * STRENGTH_MULT
+    pub fn arrow_velocity(&self, physics: &ArrowPhysics) -> f32 {
+        physics.arrow_speed(self.strength())
     }
 }
 
@@ -11,10 +11,14 @@ use crate::{
     Screen,
     gameplay::{
         GameSet, GameState,
-        arrow::{ARROW_VELOCITY_THRESHOLD, CancelArrow, FireArrow, ReadyArrow},
+        arrow::{ArrowKind, ArrowPhysics, CancelArrow, FireArrow, ReadyArrow},
         bow::{Bow, BowArrow, BowAssets, EPS, animation},
         cursor::CursorPosition,
+        gamepad::{self, pad_just_pressed, pad_just_released},
+        level::LevelState,
+        quiver::Quiver,
         scorecard::ArrowCountsTowardsScore,
+        touch,
     },
 };
 
@@ -28,18 +32,31 @@ pub(super) fn plugin(app: &mut App) {
                 update_primary_bow_transform,
                 update_primary_bow_rotation_not_pulling,
             )
-                .run_if(in_state(GameState::Playing))
+                .run_if(in_state(GameState::Playing).and(not(in_state(LevelState::Editing))))
                 .in_set(GameSet::Update),
         )
         .add_systems(
             Update,
             (
-                on_mouse_cancel.run_if(input_just_pressed(MouseButton::Right)),
-                on_mouse_up.run_if(input_just_released(MouseButton::Left)),
-                on_mouse_down.run_if(input_just_pressed(MouseButton::Left)),
+                on_mouse_cancel.run_if(
+                    input_just_pressed(MouseButton::Right)
+                        .or(pad_just_pressed(gamepad::CANCEL))
+                        .or(touch::finger_canceled),
+                ),
+                on_mouse_up.run_if(
+                    input_just_released(MouseButton::Left)
+                        .or(pad_just_released(gamepad::DRAW))
+                        .or(touch::finger_lifted),
+                ),
+                on_mouse_down.run_if(
+                    input_just_pressed(MouseButton::Left)
+                        .or(pad_just_pressed(gamepad::DRAW))
+                        .or(touch::finger_down),
+                ),
             )
-                .run_if(in_state(GameState::Playing))
-                .in_set(GameSet::RecordInput),
+                .run_if(in_state(GameState::Playing).and(not(in_state(LevelState::Editing))))
+                .in_set(GameSet::RecordInput)
+                .after(touch::track_touches),
         );
 }
 
@@ -138,9 +155,16 @@ fn update_primary_bow_rotation_not_pulling(
 
 fn on_mouse_down(
     mut commands: Commands,
-    bow: Single<Entity, (With<PrimaryBow>, Without<BowArrow>)>,
+    bow: Single<(Entity, &mut Transform), (With<PrimaryBow>, Without<BowArrow>)>,
+    cursor: Res<CursorPosition>,
+    quiver: Res<Quiver>,
 ) {
-    commands.trigger(ReadyArrow::for_bow(*bow));
+    let (bow, mut transform) = bow.into_inner();
+    // a touch puts the bow down where the finger lands, before it has had a chance to follow
+    if let Some(position) = cursor.current() {
+        transform.translation = position;
+    }
+    commands.trigger(ReadyArrow::for_bow(bow).with_kind(quiver.selected()));
 }
 fn on_mouse_cancel(mut commands: Commands, bows: Query<&BowArrow>) {
     for arrow in &bows {
@@ -148,11 +172,19 @@ fn on_mouse_cancel(mut commands: Commands, bows: Query<&BowArrow>) {
     }
 }
 
-fn on_mouse_up(mut commands: Commands, bow_arrows: Query<&BowArrow>) {
+fn on_mouse_up(
+    mut commands: Commands,
+    bow_arrows: Query<&BowArrow>,
+    kinds: Query<&ArrowKind>,
+    mut quiver: ResMut<Quiver>,
+    physics: Res<ArrowPhysics>,
+) {
     for arrow in &bow_arrows {
-        commands.trigger_targets(FireArrow, arrow.arrow());
-        if arrow.arrow_velocity() > ARROW_VELOCITY_THRESHOLD {
+        let kind = kinds.get(arrow.arrow()).copied().unwrap_or_default();
+        commands.trigger_targets(FireArrow(kind), arrow.arrow());
+        if arrow.arrow_velocity(&physics) > physics.min_speed {
             commands.trigger(ArrowCountsTowardsScore);
+            quiver.take(kind);
         }
     }
 }
@@ -4,9 +4,12 @@ use super::{Bow, BowAssets, animation};
 use crate::{
     gameplay::{
         GameState,
-        arrow::{FireArrow, ReadyArrow},
+        arrow::{ArrowKind, FireArrow, ReadyArrow},
         bow::BowArrow,
+        gamepad::{self, pad_just_released},
+        quiver::Quiver,
         timefreeze::FreezeLocation,
+        touch,
     },
     world::GAME_PLANE,
 };
@@ -15,13 +18,24 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameState::TimeFreeze), spawn_bow)
         .add_systems(
             Update,
-            on_mouse_up.run_if(
-                in_state(GameState::TimeFreeze).and(input_just_released(MouseButton::Left)),
-            ),
+            on_mouse_up
+                .run_if(
+                    in_state(GameState::TimeFreeze).and(
+                        input_just_released(MouseButton::Left)
+                            .or(pad_just_released(gamepad::DRAW))
+                            .or(touch::finger_lifted),
+                    ),
+                )
+                .after(touch::track_touches),
         );
 }
 
-pub fn spawn_bow(mut commands: Commands, bow_assets: Res<BowAssets>, freeze: Res<FreezeLocation>) {
+pub fn spawn_bow(
+    mut commands: Commands,
+    bow_assets: Res<BowAssets>,
+    freeze: Res<FreezeLocation>,
+    quiver: Res<Quiver>,
+) {
     info!("Spawning bow");
     let bow = commands
         .spawn((
@@ -35,10 +49,17 @@ pub fn spawn_bow(mut commands: Commands, bow_assets: Res<BowAssets>, freeze: Res
         .observe(animation::setup_animations)
         .id();
 
-    commands.trigger(ReadyArrow::for_bow(bow));
+    commands.trigger(ReadyArrow::for_bow(bow).with_kind(quiver.selected()));
 }
-fn on_mouse_up(mut commands: Commands, bow_arrows: Query<&BowArrow>) {
+fn on_mouse_up(
+    mut commands: Commands,
+    bow_arrows: Query<&BowArrow>,
+    kinds: Query<&ArrowKind>,
+    mut quiver: ResMut<Quiver>,
+) {
     for arrow in &bow_arrows {
-        commands.trigger_targets(FireArrow, arrow.arrow());
+        let kind = kinds.get(arrow.arrow()).copied().unwrap_or_default();
+        commands.trigger_targets(FireArrow(kind), arrow.arrow());
+        quiver.take(kind);
     }
 }
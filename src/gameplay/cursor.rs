@@ -10,8 +10,18 @@ use crate::{
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<CursorPosition>()
-        .init_resource::<CursorPosition>();
-    app.add_systems(Update, set_cursor_position.in_set(GameSet::RecordInput));
+        .register_type::<PointerSource>()
+        .init_resource::<CursorPosition>()
+        .init_resource::<PointerSource>();
+    app.add_systems(
+        Update,
+        (
+            use_mouse,
+            set_cursor_position.run_if(resource_equals(PointerSource::Mouse)),
+        )
+            .chain()
+            .in_set(GameSet::RecordInput),
+    );
     app.add_systems(Update, new_pointer_pos.in_set(GameSet::Update));
 }
 
@@ -47,6 +57,11 @@ pub struct CursorPosition {
 }
 #[allow(dead_code)]
 impl CursorPosition {
+    /// Moves the cursor to `xy` on the game plane
+    pub fn set(&mut self, xy: Vec2) {
+        self.current = Some(xy);
+        self.last = Some(xy);
+    }
     pub fn xy(&self) -> Option<Vec2> {
         self.current
     }
@@ -61,6 +76,39 @@ impl CursorPosition {
     }
 }
 
+/// What moves the cursor around
+#[derive(Resource, Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum PointerSource {
+    #[default]
+    Mouse,
+    /// the left stick moves a virtual cursor
+    Gamepad,
+    /// the finger drawing the bow
+    Touch,
+}
+
+/// Gives the cursor back to the mouse as soon as it moves
+fn use_mouse(mut moved: EventReader<CursorMoved>, mut source: ResMut<PointerSource>) {
+    if moved.read().count() > 0 {
+        source.set_if_neq(PointerSource::Mouse);
+    }
+}
+
+/// Where a point of the viewport is on the game plane
+pub fn viewport_to_plane(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    viewport_position: Vec2,
+) -> Option<Vec2> {
+    let ray = camera
+        .viewport_to_world(camera_transform, viewport_position)
+        .ok()?;
+    let distance =
+        ray.intersect_plane(Vec3::new(0., 0., GAME_PLANE), InfinitePlane3d::new(Dir3::Z))?;
+    Some(ray.get_point(distance).xy())
+}
+
 fn set_cursor_position(
     camera: Query<(&Camera, &GlobalTransform), With<WorldCamera>>,
     windows: Query<&Window>,
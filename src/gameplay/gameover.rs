@@ -1,9 +1,12 @@
-use bevy::prelude::{Val::*, *};
+use bevy::{
+    input_focus::tab_navigation::TabGroup,
+    prelude::{Val::*, *},
+};
 
 use crate::{
     Screen,
     gameplay::scorecard::{ABOVE_PAR, AT_PAR, BELOW_PAR, ScoreCard, spawn_scorecard},
-    theme::widgets,
+    theme::{interaction::OnPress, widgets},
     utils,
 };
 
@@ -46,6 +49,7 @@ fn spawn_gameover_ui(mut commands: Commands, scorecard: Res<ScoreCard>) {
             GlobalZIndex(1),
             BackgroundColor(Srgba::new(0., 0., 0., 0.8).into()),
             Pickable::default(),
+            TabGroup::new(0),
         ))
         .id();
 
@@ -138,18 +142,14 @@ fn spawn_gameover_ui(mut commands: Commands, scorecard: Res<ScoreCard>) {
     ));
 }
 
-fn return_to_title(_: Trigger<Pointer<Click>>, mut state: ResMut<NextState<Screen>>) {
+fn return_to_title(_: Trigger<OnPress>, mut state: ResMut<NextState<Screen>>) {
     state.set(Screen::Title);
 }
 
 #[derive(Resource, Default)]
 pub struct AutoReplay;
 
-fn play_again(
-    _: Trigger<Pointer<Click>>,
-    mut commands: Commands,
-    mut state: ResMut<NextState<Screen>>,
-) {
+fn play_again(_: Trigger<OnPress>, mut commands: Commands, mut state: ResMut<NextState<Screen>>) {
     state.set(Screen::Title);
     commands.init_resource::<AutoReplay>();
 }
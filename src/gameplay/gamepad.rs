@@ -0,0 +1,116 @@
+//! Plays the game with a gamepad.
+//!
+//! The left stick moves a virtual cursor that the bow follows. Holding the right trigger draws the
+//! bow where it is, the right stick pulls the string back in any direction and letting go of the
+//! trigger fires. East (B on an Xbox pad) cancels the shot, the bumpers pick arrows from the quiver
+//! and Start pauses. Any stick or button takes the cursor over from the mouse, and moving the mouse
+//! takes it back.
+
+use bevy::prelude::*;
+
+use crate::{
+    camera::WorldCamera,
+    gameplay::{
+        GameSet, GameState,
+        bow::{Bow, BowArrow, MAX_RADIUS},
+        cursor::{CursorPosition, PointerSource, viewport_to_plane},
+    },
+};
+
+/// Holding it draws the bow, letting go fires
+pub const DRAW: GamepadButton = GamepadButton::RightTrigger2;
+pub const CANCEL: GamepadButton = GamepadButton::East;
+pub const PAUSE: GamepadButton = GamepadButton::Start;
+/// Cycle through the quiver
+pub const NEXT_ARROW: GamepadButton = GamepadButton::RightTrigger;
+pub const PREVIOUS_ARROW: GamepadButton = GamepadButton::LeftTrigger;
+
+/// Stick movement smaller than this is ignored
+const DEADZONE: f32 = 0.2;
+/// World units a second the virtual cursor moves with the left stick all the way over
+const CURSOR_SPEED: f32 = 40.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            use_gamepad,
+            move_virtual_cursor.run_if(resource_equals(PointerSource::Gamepad)),
+        )
+            .chain()
+            .in_set(GameSet::RecordInput)
+            .run_if(not(in_state(GameState::Paused))),
+    );
+}
+
+/// Whether `button` was just pressed on any gamepad
+pub fn pad_just_pressed(button: GamepadButton) -> impl FnMut(Query<&Gamepad>) -> bool + Clone {
+    move |gamepads: Query<&Gamepad>| gamepads.iter().any(|pad| pad.just_pressed(button))
+}
+
+/// Whether `button` was just let go of on any gamepad
+pub fn pad_just_released(button: GamepadButton) -> impl FnMut(Query<&Gamepad>) -> bool + Clone {
+    move |gamepads: Query<&Gamepad>| gamepads.iter().any(|pad| pad.just_released(button))
+}
+
+fn outside_deadzone(stick: Vec2) -> Vec2 {
+    if stick.length() < DEADZONE {
+        Vec2::ZERO
+    } else {
+        stick.clamp_length_max(1.)
+    }
+}
+
+fn use_gamepad(gamepads: Query<&Gamepad>, mut source: ResMut<PointerSource>) {
+    let used = gamepads.iter().any(|pad| {
+        outside_deadzone(pad.left_stick()) != Vec2::ZERO
+            || outside_deadzone(pad.right_stick()) != Vec2::ZERO
+            || pad.get_just_pressed().next().is_some()
+    });
+    if used {
+        source.set_if_neq(PointerSource::Gamepad);
+    }
+}
+
+fn move_virtual_cursor(
+    gamepads: Query<&Gamepad>,
+    drawn: Query<&Transform, (With<Bow>, With<BowArrow>)>,
+    camera: Query<(&Camera, &GlobalTransform), With<WorldCamera>>,
+    source: Res<PointerSource>,
+    time: Res<Time>,
+    mut cursor: ResMut<CursorPosition>,
+    // where the bow goes when it isn't drawn
+    mut anchor: Local<Vec2>,
+) {
+    if source.is_changed() {
+        *anchor = cursor.xy_last().unwrap_or_default();
+    }
+
+    // the drawn bow stays put, and the right stick pulls the string back from it
+    if let Ok(bow) = drawn.single() {
+        let pull: Vec2 = gamepads.iter().map(|pad| pad.right_stick()).sum();
+        cursor.set(bow.translation.xy() + outside_deadzone(pull) * MAX_RADIUS);
+        return;
+    }
+
+    let stick: Vec2 = gamepads.iter().map(|pad| pad.left_stick()).sum();
+    let mut position = *anchor + outside_deadzone(stick) * CURSOR_SPEED * time.delta_secs();
+    // keep the cursor on screen
+    if let Some(view) = camera
+        .single()
+        .ok()
+        .and_then(|(camera, transform)| visible_area(camera, transform))
+    {
+        position = position.clamp(view.min, view.max);
+    }
+    *anchor = position;
+    cursor.set(position);
+}
+
+/// The part of the game plane the camera sees
+fn visible_area(camera: &Camera, transform: &GlobalTransform) -> Option<Rect> {
+    let size = camera.logical_viewport_size()?;
+    let corner = viewport_to_plane(camera, transform, Vec2::ZERO)?;
+    let opposite = viewport_to_plane(camera, transform, size)?;
+    Some(Rect::from_corners(corner, opposite))
+}
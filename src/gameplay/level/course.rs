@@ -0,0 +1,108 @@
+//! Courses are named runs of levels, picked on the title screen.
+//!
+//! The [`Levels`] resource holds the levels of the course being played. Each run of a course
+//! starts with a fresh [`ScoreCard`](crate::gameplay::scorecard::ScoreCard) built from them.
+
+use bevy::prelude::*;
+
+use crate::{
+    Screen,
+    gameplay::level::{LevelProps, Levels},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Courses>()
+        .init_resource::<CurrentCourse>()
+        .add_observer(play_course);
+}
+
+/// A named list of levels, like "Beginner" or "Mayhem"
+#[derive(Clone)]
+pub struct Course {
+    name: String,
+    levels: Levels,
+    /// how many generated levels are played after the handmade ones
+    generated: usize,
+    /// allows assists like the aim preview
+    practice: bool,
+}
+
+impl Course {
+    pub fn new(name: String, levels: Levels, generated: usize, practice: bool) -> Self {
+        Self {
+            name,
+            levels,
+            generated,
+            practice,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn is_practice(&self) -> bool {
+        self.practice
+    }
+    pub fn num_levels(&self) -> usize {
+        self.levels.num_levels() + self.generated
+    }
+
+    /// The levels for a new run of this course. Generated levels are different every run.
+    pub fn start(&self) -> Levels {
+        let mut levels = self.levels.clone();
+        levels.seed = crate::rand::random();
+        if let Some(last) = self.num_levels().checked_sub(1) {
+            levels.get_or_generate(last);
+        }
+        levels
+    }
+}
+
+/// Every course, in the order they are listed
+#[derive(Resource, Default)]
+pub struct Courses {
+    courses: Vec<Course>,
+}
+
+impl Courses {
+    pub(super) fn insert(&mut self, course: Course) {
+        self.courses.push(course);
+    }
+    /// Replaces a handmade level of a course, for runs started after this
+    pub(super) fn replace(&mut self, course: usize, level: usize, props: LevelProps) {
+        if let Some(course) = self.courses.get_mut(course) {
+            course.levels.replace(level, props);
+        }
+    }
+    pub fn get(&self, course: usize) -> Option<&Course> {
+        self.courses.get(course)
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, Course> {
+        self.courses.iter()
+    }
+}
+
+/// The index of the course being played in [`Courses`]
+#[derive(Resource, Default)]
+pub struct CurrentCourse(pub usize);
+
+/// Starts a run of a course
+#[derive(Event)]
+pub struct PlayCourse(pub usize);
+
+fn play_course(
+    trigger: Trigger<PlayCourse>,
+    mut commands: Commands,
+    courses: Res<Courses>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let index = trigger.event().0;
+    let Some(course) = courses.get(index) else {
+        error!("There is no course {index}!");
+        return;
+    };
+
+    info!("Playing {}", course.name());
+    commands.insert_resource(CurrentCourse(index));
+    commands.insert_resource(course.start());
+    next_screen.set(Screen::Transition);
+}
@@ -0,0 +1,916 @@
+//! An in-game level editor, opened from the title screen.
+//!
+//! Walls and spheres are placed on the game plane with the cursor, snapping to the same
+//! [`BLOCK_LEN`] grid that [`vert!`] and [`horz!`] use. Levels are play-tested in place and
+//! saved back to their `.level.ron` file.
+
+use std::{collections::BTreeSet, f32::consts::PI};
+
+use avian3d::prelude::{Physics, PhysicsTime};
+use bevy::{
+    color::palettes::{
+        css::{ORANGE, YELLOW},
+        tailwind::VIOLET_500,
+    },
+    input::common_conditions::input_just_pressed,
+    prelude::{Val::*, *},
+};
+
+use crate::{
+    Screen,
+    gameplay::{
+        GameSet, GameState,
+        arrow::Arrow,
+        bow::{Bow, PrimaryBow},
+        cursor::CursorPosition,
+        level::{
+            Level, LevelProps, LevelState, Levels, WallBuilder, WallMaterial, WallMesh, WallShape,
+            Walls, spawn_moving_walls, spawn_special_walls, spawn_spheres, spawn_walls,
+            sphere::{PortalLink, SpawnSphere, SphereType},
+        },
+        scorecard::ScoreCard,
+        sphere::SphereKinds,
+    },
+    theme::{interaction::OnPress, widgets},
+    world::{BLOCK_LEN, GAME_PLANE},
+};
+
+/// How close the cursor has to be to a sphere to pick it
+const SPHERE_PICK_RADIUS: f32 = 1.5;
+/// How far Q and E rotate the selected wall, or the side the selected portal faces
+const ROTATION_STEP: f32 = PI / 12.;
+
+/// The tools that come before the spheres when the palette cycles through them
+const TOOLS: [EditorTool; 4] = [
+    EditorTool::Select,
+    EditorTool::Wall,
+    EditorTool::Block,
+    EditorTool::Pole,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EditorTool>()
+        .add_systems(OnExit(Screen::Gameplay), end_session)
+        .add_systems(
+            OnEnter(LevelState::Playing),
+            open_editor.run_if(resource_exists::<EditorSession>),
+        )
+        .add_systems(
+            OnEnter(LevelState::Editing),
+            (
+                start_editing,
+                spawn_editor_ui,
+                set_bow_visibility(Visibility::Hidden),
+            ),
+        )
+        .add_systems(
+            OnExit(LevelState::Editing),
+            (stop_editing, set_bow_visibility(Visibility::Inherited)),
+        )
+        .add_systems(
+            Update,
+            stop_play_test.in_set(GameSet::RecordInput).run_if(
+                in_state(LevelState::Playing)
+                    .and(resource_exists::<EditorSession>)
+                    .and(input_just_pressed(KeyCode::Tab)),
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                keep_physics_paused,
+                (
+                    edit_keys,
+                    (press_tool, drag_tool).chain().run_if(not(pointer_over_ui)),
+                    release_tool,
+                )
+                    .chain()
+                    .in_set(GameSet::RecordInput),
+                (rebuild_edited_level, update_editor_text, draw_editor_gizmos)
+                    .chain()
+                    .in_set(GameSet::Update),
+            )
+                .run_if(in_state(LevelState::Editing).and(in_state(GameState::Playing))),
+        );
+
+    #[cfg(not(feature = "dev"))]
+    app.add_systems(OnEnter(LevelState::Editing), crate::utils::show_cursor)
+        .add_systems(
+            OnExit(LevelState::Editing),
+            crate::utils::hide_cursor.run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+/// Present while the level editor is open.
+///
+/// Finishing a level goes back to the editor instead of on to the next level.
+#[derive(Resource, Default)]
+pub struct EditorSession {
+    play_testing: bool,
+    /// levels of this run with edits that are not saved to their file yet
+    unsaved: BTreeSet<usize>,
+}
+
+/// What a left click on the game plane does
+#[derive(Resource, Clone, PartialEq, Default)]
+enum EditorTool {
+    /// Pick up and drag walls and spheres
+    #[default]
+    Select,
+    /// Drag out a wall along the grid, like [`vert!`] and [`horz!`]
+    Wall,
+    Block,
+    Pole,
+    Sphere(SphereType),
+}
+
+impl EditorTool {
+    fn name(&self) -> String {
+        match self {
+            Self::Select => "Select".to_string(),
+            Self::Wall => "Wall".to_string(),
+            Self::Block => "Block".to_string(),
+            Self::Pole => "Pole".to_string(),
+            Self::Sphere(sphere_type) => sphere_type.to_string(),
+        }
+    }
+    /// Every tool, then a sphere tool for each registered kind of sphere
+    fn cycled(&self, by: isize, kinds: &SphereKinds) -> Self {
+        let tools: Vec<_> = TOOLS
+            .into_iter()
+            .chain(kinds.iter().cloned().map(EditorTool::Sphere))
+            .collect();
+        let index = tools
+            .iter()
+            .position(|tool| tool == self)
+            .unwrap_or_default();
+        tools[(index as isize + by).rem_euclid(tools.len() as isize) as usize].clone()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Selection {
+    Wall(usize),
+    Sphere(usize),
+}
+
+/// A click that is still being held
+#[derive(Clone, Copy)]
+enum Gesture {
+    /// Dragging the selection, starting from this snapped point
+    Move { from: Vec2 },
+    /// Drawing a wall, starting from this snapped point
+    Wall { from: Vec2 },
+}
+
+/// The level being edited, and the entities spawned for it
+#[derive(Resource)]
+struct EditedLevel {
+    props: LevelProps,
+    walls: Vec<Entity>,
+    spheres: Vec<Entity>,
+    selected: Option<Selection>,
+    gesture: Option<Gesture>,
+    /// the spawned level no longer matches `props`
+    dirty: bool,
+    /// `props` has edits that are not saved to the level file yet
+    unsaved: bool,
+}
+
+impl EditedLevel {
+    /// `props` was edited, so the level has to be spawned again and saved
+    fn changed(&mut self) {
+        self.dirty = true;
+        self.unsaved = true;
+    }
+    fn item_at(&self, point: Vec2) -> Option<Selection> {
+        self.props
+            .spheres
+            .iter()
+            .rposition(|sphere| sphere.location.distance(point) <= SPHERE_PICK_RADIUS)
+            .map(Selection::Sphere)
+            .or_else(|| {
+                self.props
+                    .walls
+                    .iter()
+                    .rposition(|wall| wall.contains(point))
+                    .map(Selection::Wall)
+            })
+    }
+    fn position(&self, selection: Selection) -> Option<Vec2> {
+        match selection {
+            Selection::Wall(index) => self
+                .props
+                .walls
+                .get(index)
+                .map(|wall| wall.transform.translation.xy()),
+            Selection::Sphere(index) => self.props.spheres.get(index).map(|sphere| sphere.location),
+        }
+    }
+    fn entity(&self, selection: Selection) -> Option<Entity> {
+        match selection {
+            Selection::Wall(index) => self.walls.get(index).copied(),
+            Selection::Sphere(index) => self.spheres.get(index).copied(),
+        }
+    }
+    fn translate(&mut self, selection: Selection, delta: Vec2) {
+        match selection {
+            Selection::Wall(index) => {
+                if let Some(wall) = self.props.walls.get_mut(index) {
+                    *wall = WallBuilder::from(wall.shape.clone().translated(delta));
+                }
+            }
+            Selection::Sphere(index) => {
+                if let Some(sphere) = self.props.spheres.get_mut(index) {
+                    sphere.location += delta;
+                }
+            }
+        }
+        self.changed();
+    }
+    fn remove(&mut self, selection: Selection) {
+        match selection {
+            Selection::Wall(index) if index < self.props.walls.len() => {
+                self.props.walls.remove(index);
+            }
+            Selection::Sphere(index) if index < self.props.spheres.len() => {
+                self.props.spheres.remove(index);
+            }
+            _ => return,
+        }
+        self.selected = None;
+        self.changed();
+    }
+    fn add_wall(&mut self, shape: WallShape) {
+        self.props.walls.push(WallBuilder::from(shape));
+        self.selected = Some(Selection::Wall(self.props.walls.len() - 1));
+        self.changed();
+    }
+}
+
+/// Snaps to the centers of the block grid, or to whole units while shift is held
+fn snap(point: Vec2, keys: &ButtonInput<KeyCode>) -> Vec2 {
+    let step = if keys.pressed(KeyCode::ShiftLeft) {
+        1.
+    } else {
+        BLOCK_LEN
+    };
+    (point / step).round() * step
+}
+
+fn pointer_over_ui(interactions: Query<&Interaction>) -> bool {
+    interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
+fn end_session(mut commands: Commands) {
+    commands.remove_resource::<EditorSession>();
+    commands.remove_resource::<EditedLevel>();
+}
+
+fn open_editor(session: Res<EditorSession>, mut next_state: ResMut<NextState<LevelState>>) {
+    if !session.play_testing {
+        next_state.set(LevelState::Editing);
+    }
+}
+
+fn stop_play_test(mut next_state: ResMut<NextState<LevelState>>) {
+    next_state.set(LevelState::Editing);
+}
+
+fn keep_physics_paused(mut time: ResMut<Time<Physics>>) {
+    if !time.is_paused() {
+        time.pause();
+    }
+}
+
+fn set_bow_visibility(visibility: Visibility) -> impl Fn(Query<&mut Visibility, With<PrimaryBow>>) {
+    move |mut bows| {
+        for mut bow in &mut bows {
+            *bow = visibility;
+        }
+    }
+}
+
+fn start_editing(
+    mut commands: Commands,
+    mut session: ResMut<EditorSession>,
+    levels: Res<Levels>,
+    level: Res<Level>,
+    arrows: Query<Entity, With<Arrow>>,
+    bows: Query<Entity, (With<Bow>, Without<PrimaryBow>)>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    session.play_testing = false;
+
+    let Some(props) = levels.get(level.0) else {
+        return;
+    };
+    commands.insert_resource(EditedLevel {
+        props: props.clone(),
+        walls: Vec::new(),
+        spheres: Vec::new(),
+        selected: None,
+        gesture: None,
+        dirty: true,
+        unsaved: session.unsaved.contains(&level.0),
+    });
+
+    for arrow in arrows {
+        commands.entity(arrow).try_despawn();
+    }
+    for bow in bows {
+        commands.entity(bow).try_despawn();
+    }
+    if *game_state.get() == GameState::TimeFreeze {
+        next_game_state.set(GameState::Playing);
+    }
+}
+
+fn stop_editing(
+    mut commands: Commands,
+    edited: Option<Res<EditedLevel>>,
+    mut session: Option<ResMut<EditorSession>>,
+    mut levels: ResMut<Levels>,
+    mut scorecard: ResMut<ScoreCard>,
+    level: Res<Level>,
+    mut time: ResMut<Time<Physics>>,
+) {
+    time.unpause();
+    let Some(edited) = edited else {
+        return;
+    };
+    if let Some(course) = scorecard.get_mut(level.0) {
+        course.update_from(&edited.props);
+    }
+    // the edits last for this run, the course only gets them once they are saved
+    levels.replace(level.0, edited.props.clone());
+    if let Some(session) = &mut session {
+        if edited.unsaved {
+            session.unsaved.insert(level.0);
+        } else {
+            session.unsaved.remove(&level.0);
+        }
+    }
+    commands.remove_resource::<EditedLevel>();
+}
+
+fn edit_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut edited: ResMut<EditedLevel>,
+    mut tool: ResMut<EditorTool>,
+    kinds: Res<SphereKinds>,
+    mut level: ResMut<Level>,
+    mut levels: ResMut<Levels>,
+    mut session: ResMut<EditorSession>,
+    mut next_state: ResMut<NextState<LevelState>>,
+) {
+    if keys.just_pressed(KeyCode::Tab) {
+        session.play_testing = true;
+        next_state.set(LevelState::Playing);
+        return;
+    }
+    if keys.just_pressed(KeyCode::PageDown) {
+        // past the last handmade level, this generates a new one
+        levels.get_or_generate(level.0 + 1);
+        level.0 += 1;
+        next_state.set(LevelState::NextLevel);
+        return;
+    }
+    if keys.just_pressed(KeyCode::PageUp) && level.0 > 0 {
+        level.0 -= 1;
+        next_state.set(LevelState::NextLevel);
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::BracketLeft) {
+        *tool = tool.cycled(-1, &kinds);
+    }
+    if keys.just_pressed(KeyCode::BracketRight) {
+        *tool = tool.cycled(1, &kinds);
+    }
+
+    let Some(selected) = edited.selected else {
+        return;
+    };
+    if keys.any_just_pressed([KeyCode::Delete, KeyCode::Backspace]) {
+        edited.remove(selected);
+        return;
+    }
+
+    if let Selection::Sphere(index) = selected {
+        // the selection can outlive what it pointed at, like after a reload
+        let Some(sphere) = edited.props.spheres.get_mut(index) else {
+            edited.selected = None;
+            return;
+        };
+        let hits = sphere.hits.unwrap_or(1);
+        let mut new_hits = hits;
+        if keys.just_pressed(KeyCode::Equal) {
+            new_hits += 1;
+        }
+        if keys.just_pressed(KeyCode::Minus) {
+            new_hits = new_hits.saturating_sub(1).max(1);
+        }
+        if new_hits != hits {
+            sphere.hits = (new_hits > 1).then_some(new_hits);
+            edited.changed();
+        }
+    }
+
+    let mut angle = 0.;
+    if keys.just_pressed(KeyCode::KeyQ) {
+        angle += ROTATION_STEP;
+    }
+    if keys.just_pressed(KeyCode::KeyE) {
+        angle -= ROTATION_STEP;
+    }
+    if angle == 0. {
+        return;
+    }
+    match selected {
+        Selection::Wall(index) => {
+            let Some(wall) = edited.props.walls.get_mut(index) else {
+                edited.selected = None;
+                return;
+            };
+            *wall = WallBuilder::from(wall.rotated(angle));
+            edited.changed();
+        }
+        // portals turn the side things come out of
+        Selection::Sphere(index) => {
+            let Some(sphere) = edited.props.spheres.get_mut(index) else {
+                edited.selected = None;
+                return;
+            };
+            if let Some(portal) = &mut sphere.portal {
+                portal.facing = Vec2::from_angle(angle).rotate(portal.facing);
+                edited.changed();
+            }
+        }
+    }
+}
+
+fn press_tool(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor: Res<CursorPosition>,
+    tool: Res<EditorTool>,
+    mut edited: ResMut<EditedLevel>,
+) {
+    let Some(point) = cursor.xy() else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Right) {
+        if let Some(item) = edited.item_at(point) {
+            edited.remove(item);
+        }
+        return;
+    }
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let snapped = snap(point, &keys);
+    match &*tool {
+        EditorTool::Select => {
+            edited.selected = edited.item_at(point);
+            edited.gesture = Some(Gesture::Move { from: snapped });
+        }
+        EditorTool::Wall => {
+            edited.gesture = Some(Gesture::Wall { from: snapped });
+        }
+        EditorTool::Block => edited.add_wall(WallShape::Block {
+            x_len: BLOCK_LEN,
+            y_len: BLOCK_LEN,
+            x: snapped.x,
+            y: snapped.y,
+        }),
+        EditorTool::Pole => edited.add_wall(WallShape::Pole {
+            radius: BLOCK_LEN * 0.5,
+            x: snapped.x,
+            y: snapped.y,
+        }),
+        EditorTool::Sphere(sphere_type) => {
+            let mut sphere = SpawnSphere::new(snapped, sphere_type.clone());
+            if *sphere_type == SphereType::PORTAL {
+                sphere.portal = Some(next_portal_link(&edited.props.spheres));
+            }
+            edited.props.spheres.push(sphere);
+            edited.selected = Some(Selection::Sphere(edited.props.spheres.len() - 1));
+            edited.changed();
+        }
+    }
+}
+
+/// Links a new portal to a portal that has no pair yet, or starts a new pair
+fn next_portal_link(spheres: &[SpawnSphere]) -> PortalLink {
+    let pairs: Vec<u32> = spheres
+        .iter()
+        .filter_map(|sphere| sphere.portal.map(|link| link.pair))
+        .collect();
+    let pair = pairs
+        .iter()
+        .copied()
+        .find(|pair| pairs.iter().filter(|other| *other == pair).count() == 1)
+        .unwrap_or_else(|| pairs.iter().max().map_or(0, |max| max + 1));
+    PortalLink {
+        pair,
+        facing: Vec2::Y,
+    }
+}
+
+// moves the selected entity along with the cursor. The level itself is only changed on release.
+fn drag_tool(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor: Res<CursorPosition>,
+    edited: Res<EditedLevel>,
+    mut transforms: Query<&mut Transform>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        return;
+    }
+    let (Some(Gesture::Move { from }), Some(selected)) = (edited.gesture, edited.selected) else {
+        return;
+    };
+    let Some(point) = cursor.xy() else {
+        return;
+    };
+    let (Some(position), Some(entity)) = (edited.position(selected), edited.entity(selected))
+    else {
+        return;
+    };
+    let Ok(mut transform) = transforms.get_mut(entity) else {
+        return;
+    };
+
+    let moved = position + snap(point, &keys) - from;
+    transform.translation.x = moved.x;
+    transform.translation.y = moved.y;
+}
+
+fn release_tool(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor: Res<CursorPosition>,
+    mut edited: ResMut<EditedLevel>,
+) {
+    if !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(gesture) = edited.gesture.take() else {
+        return;
+    };
+    let Some(point) = cursor.xy() else {
+        // the drag left the window, put everything back where it was
+        edited.dirty = true;
+        return;
+    };
+    let to = snap(point, &keys);
+
+    match gesture {
+        Gesture::Move { from } => {
+            let Some(selected) = edited.selected else {
+                return;
+            };
+            if to != from {
+                edited.translate(selected, to - from);
+            }
+        }
+        Gesture::Wall { from } => {
+            // in block units, like `vert!` and `horz!`
+            let (start, end) = (from / BLOCK_LEN, to / BLOCK_LEN);
+            let delta = end - start;
+            let shape = if delta.x.abs() >= delta.y.abs() {
+                WallShape::Horz(start.y, start.x.min(end.x), start.x.max(end.x))
+            } else {
+                WallShape::Vert(start.x, start.y.min(end.y), start.y.max(end.y))
+            };
+            edited.add_wall(shape);
+        }
+    }
+}
+
+fn rebuild_edited_level(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<WallMaterial>,
+    mut edited: ResMut<EditedLevel>,
+    walls: Query<Entity, With<Walls>>,
+    spheres: Query<Entity, With<SphereType>>,
+) {
+    if !edited.dirty {
+        return;
+    }
+    let Ok(walls) = walls.single() else {
+        return;
+    };
+
+    commands.entity(walls).despawn_related::<Children>();
+    edited.walls = spawn_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        walls,
+        &edited.props.walls,
+    );
+    // these can only be changed in the level file
+    spawn_special_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        walls,
+        &edited.props.special_walls,
+    );
+    spawn_moving_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        walls,
+        &edited.props.moving_walls,
+    );
+
+    for sphere in spheres {
+        commands.entity(sphere).despawn();
+    }
+    edited.spheres = spawn_spheres(&mut commands, &edited.props.spheres, GAME_PLANE);
+
+    edited.dirty = false;
+}
+
+fn draw_editor_gizmos(
+    mut gizmos: Gizmos,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor: Res<CursorPosition>,
+    tool: Res<EditorTool>,
+    edited: Res<EditedLevel>,
+) {
+    // just above the top of the walls
+    let top = BLOCK_LEN * 0.5 + 0.1;
+    let grid_color = Color::WHITE.with_alpha(0.1);
+    // an odd number of cells puts the wall centers in the middle of a cell
+    gizmos.grid(
+        Isometry3d::from_translation(Vec3::Z * top),
+        UVec2::new(25, 15),
+        Vec2::splat(BLOCK_LEN),
+        grid_color,
+    );
+
+    if let Some(selected) = edited.selected {
+        let color = Color::from(YELLOW);
+        match selected {
+            Selection::Wall(index) => {
+                if let Some(wall) = edited.props.walls.get(index) {
+                    let isometry = Isometry3d::new(
+                        wall.transform.translation.with_z(top),
+                        wall.transform.rotation,
+                    );
+                    match &wall.mesh {
+                        WallMesh::Cuboid(cuboid) => {
+                            gizmos.rect(isometry, cuboid.half_size.xy() * 2., color);
+                        }
+                        WallMesh::Cylinder(cylinder) => {
+                            gizmos.circle(isometry, cylinder.base_shape.radius, color);
+                        }
+                        WallMesh::Prism(parts) => {
+                            for outline in parts {
+                                let points = outline.iter().chain(outline.first()).map(|point| {
+                                    wall.transform.transform_point(point.extend(0.)).with_z(top)
+                                });
+                                gizmos.linestrip(points, color);
+                            }
+                        }
+                    }
+                }
+            }
+            Selection::Sphere(index) => {
+                if let Some(sphere) = edited.props.spheres.get(index) {
+                    gizmos.circle(
+                        Isometry3d::from_translation(sphere.location.extend(top)),
+                        SPHERE_PICK_RADIUS,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+
+    // joins up the portals of each pair
+    let portals: Vec<_> = edited
+        .props
+        .spheres
+        .iter()
+        .filter_map(|sphere| Some((sphere.portal?.pair, sphere.location)))
+        .collect();
+    for (index, (pair, from)) in portals.iter().enumerate() {
+        for (other_pair, to) in &portals[index + 1..] {
+            if pair == other_pair {
+                gizmos.line(from.extend(top), to.extend(top), Color::from(VIOLET_500));
+            }
+        }
+    }
+
+    let Some(point) = cursor.xy() else {
+        return;
+    };
+    let snapped = snap(point, &keys).extend(top);
+    let color = Color::from(ORANGE);
+    match (&*tool, edited.gesture) {
+        (_, Some(Gesture::Wall { from })) => {
+            gizmos.line(from.extend(top), snapped, color);
+        }
+        (EditorTool::Wall | EditorTool::Block, _) => {
+            gizmos.rect(
+                Isometry3d::from_translation(snapped),
+                Vec2::splat(BLOCK_LEN),
+                color,
+            );
+        }
+        (EditorTool::Pole, _) => {
+            gizmos.circle(
+                Isometry3d::from_translation(snapped),
+                BLOCK_LEN * 0.5,
+                color,
+            );
+        }
+        _ => {
+            gizmos.circle(Isometry3d::from_translation(snapped), 1., color);
+        }
+    }
+}
+
+#[derive(Component)]
+enum EditorText {
+    Level,
+    Tool,
+    Par,
+}
+
+fn spawn_editor_ui(mut commands: Commands) {
+    let row = || Node {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Px(10.),
+        ..default()
+    };
+    #[cfg_attr(target_family = "wasm", allow(unused_variables))]
+    let panel = commands
+        .spawn((
+            Name::new("Level Editor"),
+            StateScoped(LevelState::Editing),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Px(20.),
+                top: Px(100.),
+                flex_direction: FlexDirection::Column,
+                row_gap: Px(10.),
+                ..default()
+            },
+            children![
+                (widgets::header("Level"), EditorText::Level),
+                (
+                    Name::new("Tool"),
+                    row(),
+                    children![
+                        widgets::button_small("<", previous_tool),
+                        (widgets::label(""), EditorText::Tool),
+                        widgets::button_small(">", next_tool),
+                    ]
+                ),
+                (
+                    Name::new("Par"),
+                    row(),
+                    children![
+                        widgets::button_small("-", lower_par),
+                        (widgets::label(""), EditorText::Par),
+                        widgets::button_small("+", raise_par),
+                    ]
+                ),
+                widgets::label("Shift: fine snap"),
+                widgets::label("[/]: tool"),
+                widgets::label("Q/E: rotate, Del: delete"),
+                widgets::label("-/=: sphere hit points"),
+                widgets::label("PgUp/PgDn: level"),
+                widgets::label("Tab: play / edit"),
+                widgets::button_base("Play", play_test, panel_button()),
+            ],
+        ))
+        .id();
+
+    #[cfg(not(target_family = "wasm"))]
+    commands.spawn((
+        widgets::button_base("Save", save_level, panel_button()),
+        ChildOf(panel),
+    ));
+}
+
+fn panel_button() -> impl Bundle {
+    (
+        Node {
+            width: Px(160.0),
+            height: Px(60.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BorderRadius::MAX,
+    )
+}
+
+fn update_editor_text(
+    tool: Res<EditorTool>,
+    edited: Res<EditedLevel>,
+    level: Res<Level>,
+    mut texts: Query<(&mut Text, &EditorText)>,
+) {
+    if !tool.is_changed() && !edited.is_changed() && !level.is_changed() {
+        return;
+    }
+    for (mut text, kind) in &mut texts {
+        text.0 = match kind {
+            EditorText::Level if edited.unsaved => format!("Level {} (unsaved)", *level),
+            EditorText::Level => format!("Level {}", *level),
+            EditorText::Tool => tool.name(),
+            EditorText::Par => format!("Par {}", edited.props.par()),
+        };
+    }
+}
+
+fn previous_tool(_: Trigger<OnPress>, mut tool: ResMut<EditorTool>, kinds: Res<SphereKinds>) {
+    *tool = tool.cycled(-1, &kinds);
+}
+
+fn next_tool(_: Trigger<OnPress>, mut tool: ResMut<EditorTool>, kinds: Res<SphereKinds>) {
+    *tool = tool.cycled(1, &kinds);
+}
+
+fn lower_par(_: Trigger<OnPress>, mut edited: ResMut<EditedLevel>) {
+    edited.props.course_par = (edited.props.course_par - 1).max(1);
+    edited.unsaved = true;
+}
+
+fn raise_par(_: Trigger<OnPress>, mut edited: ResMut<EditedLevel>) {
+    edited.props.course_par += 1;
+    edited.unsaved = true;
+}
+
+fn play_test(
+    _: Trigger<OnPress>,
+    mut session: ResMut<EditorSession>,
+    mut next_state: ResMut<NextState<LevelState>>,
+) {
+    session.play_testing = true;
+    next_state.set(LevelState::Playing);
+}
+
+/// Writes the edited level back over the file it was loaded from, and into the course for the
+/// next time it is played
+#[cfg(not(target_family = "wasm"))]
+fn save_level(
+    _: Trigger<OnPress>,
+    mut edited: ResMut<EditedLevel>,
+    mut courses: ResMut<super::Courses>,
+    level: Res<Level>,
+    course: Res<super::CurrentCourse>,
+    files: Res<super::loader::LevelFiles>,
+    lists: Res<Assets<super::loader::CourseList>>,
+) {
+    use bevy::asset::io::file::FileAssetReader;
+
+    let Some(path) = lists
+        .get(&files.list)
+        .and_then(|list| list.courses.get(course.0))
+        .and_then(|course| course.levels.get(level.0))
+        .and_then(|handle| handle.path())
+    else {
+        error!("Level {} has no file to save to!", *level);
+        return;
+    };
+    let file = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(path.path());
+
+    let ron = match ron::ser::to_string_pretty(&edited.props, ron::ser::PrettyConfig::default()) {
+        Ok(ron) => ron,
+        Err(err) => {
+            error!("Could not serialize level {}: {err}", *level);
+            return;
+        }
+    };
+    match std::fs::write(&file, ron) {
+        Ok(()) => {
+            info!("Saved level {} to {}", *level, file.display());
+            courses.replace(course.0, level.0, edited.props.clone());
+            edited.unsaved = false;
+        }
+        Err(err) => error!(
+            "Could not save level {} to {}: {err}",
+            *level,
+            file.display()
+        ),
+    }
+}
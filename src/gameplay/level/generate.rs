@@ -0,0 +1,218 @@
+//! Generates levels from a seed, for playing past the handmade levels.
+//!
+//! The same seed and [`Difficulty`] always give the same level.
+
+use rand::{Rng, seq::IndexedRandom};
+use rand_chacha::ChaCha8Rng;
+
+use bevy::prelude::*;
+
+use crate::{
+    gameplay::{
+        level::{
+            LevelProps, WallBuilder, WallShape,
+            sphere::{SpawnSphere, SphereType},
+            validate::play_area,
+        },
+        sphere::{EXPLOSION_RADIUS, SPHERE_RADIUS},
+    },
+    rand::seeded_rng,
+    world::BLOCK_LEN,
+};
+
+/// The most walls a level can have, at a wall density of 1
+const MAX_WALLS: usize = 12;
+/// Space kept free between a sphere and anything else
+const SPHERE_MARGIN: f32 = 1.;
+/// How often to try placing a sphere before giving up on it
+const PLACEMENT_ATTEMPTS: usize = 32;
+
+/// How hard a generated level is
+#[derive(Clone, Debug)]
+pub struct Difficulty {
+    /// How many walls to place, from 0 to 1
+    pub wall_density: f32,
+    /// How likely each sphere type is, relative to each other
+    pub sphere_mix: Vec<(SphereType, f32)>,
+    /// The chance for each group of spheres to be packed around an exploder, from 0 to 1
+    pub chain_reactions: f32,
+    /// How many groups of spheres to place
+    pub sphere_groups: usize,
+}
+
+impl Difficulty {
+    /// The difficulty of the `generated`th level after the handmade ones. Gets harder every level.
+    pub fn ramp(generated: usize) -> Self {
+        // goes from 0 towards 1
+        let t = 1. - 0.8_f32.powi(generated as i32);
+        Self {
+            wall_density: 0.25 + 0.5 * t,
+            sphere_mix: vec![
+                (SphereType::NORMAL, 6. - 4. * t),
+                (SphereType::MULTIPLIER, 1. + t),
+                (SphereType::BOUNCY, 1. + t),
+                (SphereType::EXPLODER, 0.5 + t),
+                (SphereType::TIME_FREEZE, 0.5 * t),
+                (SphereType::GRAVITY, 0.5 * t),
+            ],
+            chain_reactions: 0.2 + 0.4 * t,
+            sphere_groups: 4 + (6. * t) as usize,
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::ramp(0)
+    }
+}
+
+/// Makes a [`LevelProps`] out of a seed
+pub struct LevelGenerator {
+    rng: ChaCha8Rng,
+    difficulty: Difficulty,
+    walls: Vec<WallBuilder>,
+    spheres: Vec<SpawnSphere>,
+}
+
+impl LevelGenerator {
+    pub fn new(seed: u64, difficulty: Difficulty) -> Self {
+        Self {
+            rng: seeded_rng(seed),
+            difficulty,
+            walls: Vec::new(),
+            spheres: Vec::new(),
+        }
+    }
+
+    pub fn generate(mut self) -> LevelProps {
+        let wall_count = (self.difficulty.wall_density * MAX_WALLS as f32).round() as usize;
+        for _ in 0..wall_count {
+            let wall = self.random_wall();
+            self.walls.push(wall);
+        }
+
+        let mut par = 0;
+        for _ in 0..self.difficulty.sphere_groups {
+            let placed = if self.rng.random_bool(self.difficulty.chain_reactions as f64) {
+                self.place_chain_reaction()
+            } else {
+                self.place_sphere()
+            };
+            if placed {
+                par += 1;
+            }
+        }
+
+        // every level needs something to break
+        if self
+            .spheres
+            .iter()
+            .all(|sphere| sphere.sphere_type == SphereType::ABSORBER)
+        {
+            if let Some(sphere) = self.spheres.first_mut() {
+                sphere.sphere_type = SphereType::NORMAL;
+            } else {
+                // nothing fit, so make room
+                self.walls.clear();
+                self.place(SphereType::NORMAL);
+            }
+        }
+
+        // a good shot clears more than one group
+        let par = (par as f32 * 0.5).ceil().max(1.) as i32;
+        LevelProps::new(par, self.walls, self.spheres)
+    }
+
+    /// A straight wall along the block grid, like [`vert!`] and [`horz!`]
+    fn random_wall(&mut self) -> WallBuilder {
+        let area = play_area();
+        // in block units, leaving room for half a block on each end
+        let max_x = ((area.max.x - BLOCK_LEN) / BLOCK_LEN).floor() as i32;
+        let max_y = ((area.max.y - BLOCK_LEN) / BLOCK_LEN).floor() as i32;
+        let length = self.rng.random_range(1..=4);
+
+        let shape = if self.rng.random_bool(0.5) {
+            let y = self.rng.random_range(-max_y..=max_y);
+            let start_x = self.rng.random_range(-max_x..=max_x - length);
+            WallShape::Horz(y as f32, start_x as f32, (start_x + length) as f32)
+        } else {
+            let x = self.rng.random_range(-max_x..=max_x);
+            let start_y = self.rng.random_range(-max_y..=max_y - length);
+            WallShape::Vert(x as f32, start_y as f32, (start_y + length) as f32)
+        };
+        WallBuilder::from(shape)
+    }
+
+    fn random_sphere_type(&mut self) -> SphereType {
+        self.difficulty
+            .sphere_mix
+            .choose_weighted(&mut self.rng, |(_, weight)| *weight)
+            .map(|(sphere_type, _)| sphere_type.clone())
+            .unwrap_or(SphereType::NORMAL)
+    }
+
+    /// Whether a sphere fits at `location` without touching anything
+    fn fits(&self, location: Vec2) -> bool {
+        let area = play_area();
+        area.contains(location)
+            && self
+                .walls
+                .iter()
+                .all(|wall| wall.distance(location) >= SPHERE_RADIUS + SPHERE_MARGIN)
+            && self.spheres.iter().all(|sphere| {
+                sphere.location.distance(location) >= SPHERE_RADIUS * 2. + SPHERE_MARGIN
+            })
+    }
+
+    fn random_location(&mut self) -> Option<Vec2> {
+        let area = play_area();
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let location = Vec2::new(
+                self.rng.random_range(area.min.x..=area.max.x),
+                self.rng.random_range(area.min.y..=area.max.y),
+            )
+            .round();
+            if self.fits(location) {
+                return Some(location);
+            }
+        }
+        None
+    }
+
+    fn place(&mut self, sphere_type: SphereType) -> bool {
+        let Some(location) = self.random_location() else {
+            return false;
+        };
+        self.spheres.push(SpawnSphere::new(location, sphere_type));
+        true
+    }
+
+    fn place_sphere(&mut self) -> bool {
+        let sphere_type = self.random_sphere_type();
+        self.place(sphere_type)
+    }
+
+    /// An exploder with a ring of spheres close enough to be caught in the blast
+    fn place_chain_reaction(&mut self) -> bool {
+        if !self.place(SphereType::EXPLODER) {
+            return false;
+        }
+        let center = self.spheres[self.spheres.len() - 1].location;
+
+        let count = self.rng.random_range(2..=5);
+        let start = self.rng.random_range(0. ..std::f32::consts::TAU);
+        for index in 0..count {
+            let angle = start + std::f32::consts::TAU * index as f32 / count as f32;
+            let distance = self
+                .rng
+                .random_range(EXPLOSION_RADIUS * 0.5..EXPLOSION_RADIUS);
+            let location = (center + Vec2::from_angle(angle) * distance).round();
+            if self.fits(location) {
+                let sphere_type = self.random_sphere_type();
+                self.spheres.push(SpawnSphere::new(location, sphere_type));
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,78 @@
+//! Rebuilds the level being played whenever its level file changes on disk.
+//!
+//! File watching comes from bevy's `file_watcher` feature, which `dev_native` turns on.
+
+use bevy::prelude::*;
+
+use crate::gameplay::{
+    level::{
+        Courses, CurrentCourse, Level, LevelProps, Levels, RebuildLevel,
+        loader::{CourseList, LevelFiles},
+    },
+    scorecard::ScoreCard,
+    sphere::SphereKinds,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_modified_levels
+            .run_if(resource_exists::<LevelFiles>.and(on_event::<AssetEvent<LevelProps>>)),
+    );
+}
+
+fn update_modified_levels(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<LevelProps>>,
+    files: Res<LevelFiles>,
+    lists: Res<Assets<CourseList>>,
+    props: Res<Assets<LevelProps>>,
+    mut courses: ResMut<Courses>,
+    current: Res<CurrentCourse>,
+    mut levels: ResMut<Levels>,
+    mut scorecard: ResMut<ScoreCard>,
+    level: Res<Level>,
+    kinds: Res<SphereKinds>,
+) {
+    let Some(list) = lists.get(&files.list) else {
+        return;
+    };
+
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(modified) = props.get(*id) else {
+            continue;
+        };
+        let mut modified = modified.clone();
+        modified.intern_sphere_types(&kinds);
+        // the same level file can be part of several courses
+        for (course, files) in list.courses.iter().enumerate() {
+            let Some(index) = files.levels.iter().position(|handle| handle.id() == *id) else {
+                continue;
+            };
+            info!(
+                "{} level {} changed on disk, reloading",
+                files.name,
+                index + 1
+            );
+            for problem in modified.problems(&kinds) {
+                warn!("{} level {}: {problem}", files.name, index + 1);
+            }
+            courses.replace(course, index, modified.clone());
+
+            if course != current.0 {
+                continue;
+            }
+            if let Some(score) = scorecard.get_mut(index) {
+                score.update_from(&modified);
+            }
+            levels.replace(index, modified.clone());
+
+            if index == level.0 {
+                commands.trigger(RebuildLevel);
+            }
+        }
+    }
+}
@@ -1,15 +1,14 @@
 use std::f32::consts::FRAC_PI_4;
 
-use avian3d::prelude::*;
 use bevy::prelude::*;
 use bevy_simple_subsecond_system::hot;
 
 use crate::gameplay::level::wall::WallBuilder;
 use crate::gameplay::level::{
-    Level, LevelProps, LevelState, WallMaterial, WallMesh, Walls, sphere::SphereType,
+    Level, LevelProps, LevelState, WallMaterial, Walls, sphere::SphereType, spawn_moving_walls,
+    spawn_special_walls, spawn_spheres, spawn_walls,
 };
 use crate::settings::Settings;
-use crate::third_party::avian3d::GameLayer;
 use crate::world::GAME_PLANE;
 
 // this will hot reload level 0 forever
@@ -32,34 +31,14 @@ fn inner(
     let props = edit_level();
 
     commands.entity(walls).despawn_related::<Children>();
-
-    for wall in props.walls.iter() {
-        let collider = wall.collider.clone();
-        let mesh = match wall.mesh {
-            WallMesh::Cuboid(cuboid) => meshes.add(cuboid),
-            WallMesh::Cylinder(cylinder) => meshes.add(cylinder),
-        };
-        let material = material.0.clone();
-        commands.spawn((
-            Mesh3d(mesh),
-            collider,
-            MeshMaterial3d(material),
-            CollisionLayers::new(GameLayer::Walls, GameLayer::all_bits()),
-            wall.transform,
-            ChildOf(walls),
-        ));
-    }
+    spawn_walls(&mut commands, meshes, material, walls, &props.walls);
+    spawn_special_walls(&mut commands, meshes, material, walls, &props.special_walls);
+    spawn_moving_walls(&mut commands, meshes, material, walls, &props.moving_walls);
 
     for sphere in spheres {
         commands.entity(sphere).despawn();
     }
-
-    for sphere in props.spheres.iter() {
-        commands.spawn((
-            sphere.sphere_type,
-            Transform::from_xyz(sphere.location.x, sphere.location.y, GAME_PLANE),
-        ));
-    }
+    spawn_spheres(&mut commands, &props.spheres, GAME_PLANE);
 }
 
 #[hot]
@@ -0,0 +1,197 @@
+//! Loads levels from `assets/levels` instead of compiling them in.
+//!
+//! A `.courses.ron` file is a list of named courses, each an ordered list of `.level.ron`
+//! paths. Each `.level.ron` file is a serialized [`LevelProps`].
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    gameplay::{
+        arrow::ArrowPhysics,
+        level::{Course, Courses, LevelProps, Levels},
+        sphere::SphereKinds,
+    },
+};
+
+/// Every course that can be played
+pub(super) const COURSE_LIST_PATH: &str = "levels/main.courses.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<LevelProps>()
+        .init_asset::<CourseList>()
+        .init_asset_loader::<LevelLoader>()
+        .init_asset_loader::<CourseListLoader>()
+        .register_type::<LevelFiles>()
+        .load_resource::<LevelFiles>()
+        .add_systems(Update, insert_courses.run_if(resource_added::<LevelFiles>));
+}
+
+/// Every course, in the order they are listed
+#[derive(Asset, TypePath)]
+pub struct CourseList {
+    pub courses: Vec<CourseFiles>,
+}
+
+/// The files of a single course
+pub struct CourseFiles {
+    pub name: String,
+    pub levels: Vec<Handle<LevelProps>>,
+    pub generated: usize,
+    pub practice: bool,
+    pub physics: ArrowPhysics,
+}
+
+/// A course as it is written in a `.courses.ron` file
+#[derive(Deserialize)]
+pub(super) struct CourseDescription {
+    pub name: String,
+    /// paths of the handmade levels, played in order
+    #[serde(default)]
+    pub levels: Vec<String>,
+    /// how many generated levels are played after the handmade ones
+    #[serde(default)]
+    pub generated: usize,
+    /// allows assists like the aim preview
+    #[serde(default)]
+    pub practice: bool,
+    /// how arrows fly in every level of the course, unless a level says otherwise
+    #[serde(default)]
+    pub physics: ArrowPhysics,
+}
+
+#[derive(Resource, Asset, Reflect, Clone)]
+pub struct LevelFiles {
+    #[dependency]
+    pub list: Handle<CourseList>,
+}
+
+impl FromWorld for LevelFiles {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            list: assets.load(COURSE_LIST_PATH),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum LevelLoaderError {
+    #[error("Could not read level file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse level file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+#[derive(Default)]
+struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = LevelProps;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+#[derive(Default)]
+struct CourseListLoader;
+
+impl AssetLoader for CourseListLoader {
+    type Asset = CourseList;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let descriptions: Vec<CourseDescription> = ron::de::from_bytes(&bytes)?;
+
+        let courses = descriptions
+            .into_iter()
+            .map(|course| CourseFiles {
+                name: course.name,
+                levels: course
+                    .levels
+                    .into_iter()
+                    .map(|path| load_context.load(path))
+                    .collect(),
+                generated: course.generated,
+                practice: course.practice,
+                physics: course.physics,
+            })
+            .collect();
+
+        Ok(CourseList { courses })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["courses.ron"]
+    }
+}
+
+// `LevelFiles` is only inserted once every level file has loaded.
+fn insert_courses(
+    mut commands: Commands,
+    files: Res<LevelFiles>,
+    lists: Res<Assets<CourseList>>,
+    levels: Res<Assets<LevelProps>>,
+    kinds: Res<SphereKinds>,
+) {
+    let Some(list) = lists.get(&files.list) else {
+        error!("Course list was not loaded!");
+        return;
+    };
+
+    let mut courses = Courses::default();
+    for files in list.courses.iter() {
+        let mut loaded = Levels {
+            physics: files.physics,
+            ..default()
+        };
+        for (index, handle) in files.levels.iter().enumerate() {
+            let Some(props) = levels.get(handle) else {
+                error!("Level {:?} was not loaded!", handle.path());
+                continue;
+            };
+            for problem in props.problems(&kinds) {
+                warn!("{} level {}: {problem}", files.name, index + 1);
+            }
+            let mut props = props.clone();
+            props.intern_sphere_types(&kinds);
+            loaded.insert(props);
+        }
+        courses.insert(Course::new(
+            files.name.clone(),
+            loaded,
+            files.generated,
+            files.practice,
+        ));
+    }
+
+    commands.insert_resource(courses);
+}
@@ -1,13 +1,18 @@
-use std::{f32::consts::FRAC_PI_4, fmt};
+use std::{collections::BTreeMap, fmt};
 
+use avian3d::prelude::{CollisionLayers, PhysicsLayer, RigidBody};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Screen,
     gameplay::{
         GAMEPLAY_CAMERA_OFFSET,
+        arrow::{ArrowKind, ArrowPhysics},
         level::{sphere::SpawnSphere, wall::WallBuilder},
+        sphere::{BreakOrder, HitPoints, SphereKinds},
     },
+    third_party::avian3d::GameLayer,
     world::GAME_PLANE,
 };
 
@@ -16,38 +21,236 @@ mod wall;
 pub use wall::*;
 #[macro_use]
 mod sphere;
+pub use sphere::{PortalLink, SphereType};
+mod course;
 #[cfg(all(feature = "dev", feature = "hot"))]
 mod level_maker;
+pub use course::{Course, Courses, CurrentCourse, PlayCourse};
+mod editor;
+pub use editor::EditorSession;
+mod generate;
+pub use generate::{Difficulty, LevelGenerator};
+#[cfg(feature = "dev_native")]
+mod hot_reload;
+mod loader;
+mod moving_sphere;
+pub use moving_sphere::{MovingSphere, SphereMotion};
+mod moving_wall;
+use moving_wall::spawn_moving_walls;
+pub use moving_wall::{MovingWall, SpawnMovingWall};
 mod new_level;
 mod next_level;
 mod timer;
+mod wall_kind;
+pub use wall_kind::{HitWall, SpawnSpecialWall, WallKind};
+use wall_kind::spawn_special_walls;
+mod solver;
+pub use solver::{ParReport, Shot, Solution, SolverError, SolverSettings, solve_level_files};
+mod validate;
+pub use validate::{LevelProblem, validate_level_files};
 
 const WALL_START_PLANE: f32 = GAMEPLAY_CAMERA_OFFSET + 20.;
 const SPHERE_START_PLANE: f32 = GAME_PLANE - 20.;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((new_level::plugin, next_level::plugin, timer::plugin));
+    app.add_plugins((
+        loader::plugin,
+        course::plugin,
+        moving_wall::plugin,
+        moving_sphere::plugin,
+        wall_kind::plugin,
+        editor::plugin,
+        new_level::plugin,
+        next_level::plugin,
+        timer::plugin,
+    ));
     app.add_sub_state::<LevelState>()
         .init_resource::<Level>()
-        .insert_resource(Levels::init());
+        .init_resource::<Levels>();
     app.add_systems(Startup, setup_wall_material)
         .add_systems(OnEnter(Screen::Gameplay), reset_level)
-        .add_observer(sphere::spawn_sphere);
+        .add_observer(sphere::spawn_sphere)
+        .add_observer(rebuild_level);
 
     #[cfg(all(feature = "dev", feature = "hot"))]
     app.add_plugins(level_maker::plugin);
+
+    #[cfg(feature = "dev_native")]
+    app.add_plugins(hot_reload::plugin);
 }
 
+/// The look of each [`WallKind`]
 #[derive(Resource)]
-struct WallMaterial(Handle<StandardMaterial>);
+struct WallMaterial {
+    plain: Handle<StandardMaterial>,
+    bouncy: Handle<StandardMaterial>,
+    sticky: Handle<StandardMaterial>,
+    breakable: Handle<StandardMaterial>,
+    one_way: Handle<StandardMaterial>,
+}
+
+impl WallMaterial {
+    fn get(&self, kind: WallKind) -> Handle<StandardMaterial> {
+        match kind {
+            WallKind::Plain => self.plain.clone(),
+            WallKind::Bouncy => self.bouncy.clone(),
+            WallKind::Sticky => self.sticky.clone(),
+            WallKind::Breakable { .. } => self.breakable.clone(),
+            WallKind::OneWay { .. } => self.one_way.clone(),
+        }
+    }
+}
 
 fn setup_wall_material(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
-    let material = materials.add(StandardMaterial {
-        base_color: Color::WHITE,
-        ..default()
+    let mut material = |kind: WallKind| {
+        materials.add(StandardMaterial {
+            base_color: kind.color(),
+            ..default()
+        })
+    };
+
+    commands.insert_resource(WallMaterial {
+        plain: material(WallKind::Plain),
+        bouncy: material(WallKind::Bouncy),
+        sticky: material(WallKind::Sticky),
+        breakable: material(WallKind::Breakable { hits: 1 }),
+        one_way: materials.add(StandardMaterial {
+            base_color: WallKind::OneWay { facing: Vec2::Y }.color(),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
     });
+}
+
+/// Spawns the walls of a level as children of the [`Walls`] entity `root`.
+///
+/// Returns the spawned walls in the same order as `walls`.
+fn spawn_walls(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: &WallMaterial,
+    root: Entity,
+    walls: &[WallBuilder],
+) -> Vec<Entity> {
+    walls
+        .iter()
+        .map(|wall| {
+            commands
+                .spawn((
+                    wall_bundle(meshes, material, wall, WallKind::Plain),
+                    ChildOf(root),
+                ))
+                .id()
+        })
+        .collect()
+}
+
+/// The mesh and collider of a single wall made of `kind`
+fn wall_bundle(
+    meshes: &mut Assets<Mesh>,
+    material: &WallMaterial,
+    wall: &WallBuilder,
+    kind: WallKind,
+) -> impl Bundle + use<> {
+    (
+        Mesh3d(meshes.add(wall.build_mesh())),
+        wall.mesh.clone(),
+        MeshMaterial3d(material.get(kind)),
+        wall.collider.clone(),
+        CollisionLayers::new(GameLayer::Walls, GameLayer::all_bits()),
+        wall.transform,
+        kind,
+    )
+}
 
-    commands.insert_resource(WallMaterial(material));
+/// Spawns the spheres of a level on the `z` plane.
+///
+/// Returns the spawned spheres in the same order as `spheres`.
+fn spawn_spheres(commands: &mut Commands, spheres: &[SpawnSphere], z: f32) -> Vec<Entity> {
+    spheres
+        .iter()
+        .map(|sphere| {
+            let mut entity = commands.spawn((
+                sphere.sphere_type.clone(),
+                Transform::from_xyz(sphere.location.x, sphere.location.y, z),
+            ));
+            if let Some(portal) = sphere.portal {
+                entity.insert(portal);
+            }
+            if let Some(hits) = sphere.hits {
+                entity.insert(HitPoints(hits));
+            }
+            // after the sphere's kind, so these replace the kind's own field and blast
+            if let Some(field) = sphere.field {
+                entity.insert(field);
+            }
+            if let Some(blast) = sphere.blast {
+                entity.insert(blast);
+            }
+            if let Some(order) = sphere.order {
+                entity.insert(BreakOrder(order));
+            }
+            if let Some(hazard) = sphere.hazard {
+                entity.insert(hazard);
+            }
+            if let Some(motion) = &sphere.motion {
+                entity.insert((
+                    MovingSphere::new(motion.clone(), sphere.location),
+                    RigidBody::Kinematic,
+                ));
+            }
+            entity.id()
+        })
+        .collect()
+}
+
+/// Respawns the walls and spheres of the current level from [`Levels`]
+#[derive(Event)]
+pub struct RebuildLevel;
+
+fn rebuild_level(
+    _: Trigger<RebuildLevel>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<WallMaterial>,
+    levels: Res<Levels>,
+    level: Res<Level>,
+    level_state: Option<Res<State<LevelState>>>,
+    walls: Query<Entity, With<Walls>>,
+    spheres: Query<Entity, With<SphereType>>,
+) {
+    // a level that is still being set up or torn down will pick up the new props on its own
+    if level_state.is_none_or(|state| *state.get() != LevelState::Playing) {
+        return;
+    }
+    let Ok(walls) = walls.single() else {
+        return;
+    };
+    let Some(props) = levels.get(level.0) else {
+        return;
+    };
+
+    commands.entity(walls).despawn_related::<Children>();
+    spawn_walls(&mut commands, &mut meshes, &material, walls, &props.walls);
+    spawn_special_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        walls,
+        &props.special_walls,
+    );
+    spawn_moving_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        walls,
+        &props.moving_walls,
+    );
+
+    for sphere in spheres {
+        commands.entity(sphere).despawn();
+    }
+    spawn_spheres(&mut commands, &props.spheres, GAME_PLANE);
 }
 
 #[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default, Reflect)]
@@ -58,6 +261,8 @@ pub(crate) enum LevelState {
     NewLevel,
     Playing,
     NextLevel,
+    /// The level is open in the level editor
+    Editing,
 }
 
 #[derive(Resource)]
@@ -76,340 +281,126 @@ impl Default for Level {
     }
 }
 
+/// A single level. These are loaded from `.level.ron` files in `assets/levels`.
+#[derive(Asset, TypePath, Clone, Serialize, Deserialize)]
 pub struct LevelProps {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    author: String,
+    /// shown under the level name while playing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+    /// the sphere type this level is the first to use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    introduces: Option<SphereType>,
+    #[serde(rename = "par")]
     course_par: i32,
     walls: Vec<WallBuilder>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    special_walls: Vec<SpawnSpecialWall>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    moving_walls: Vec<SpawnMovingWall>,
     spheres: Vec<SpawnSphere>,
+    /// how many of each special arrow the player gets, on top of the normal ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    quiver: BTreeMap<ArrowKind, u32>,
+    /// how arrows fly in this level, instead of how they fly in the rest of the course
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    physics: Option<ArrowPhysics>,
 }
 
 impl LevelProps {
     pub fn new(course_par: i32, walls: Vec<WallBuilder>, spheres: Vec<SpawnSphere>) -> Self {
         Self {
+            name: String::new(),
+            author: String::new(),
+            hint: None,
+            introduces: None,
             course_par,
             walls,
+            special_walls: Vec::new(),
+            moving_walls: Vec::new(),
             spheres,
+            quiver: BTreeMap::new(),
+            physics: None,
         }
     }
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
     pub fn par(&self) -> i32 {
         self.course_par
     }
+    /// The name of the level, or an empty string if it has none
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
+    pub fn introduces(&self) -> Option<&SphereType> {
+        self.introduces.as_ref()
+    }
+    pub fn quiver(&self) -> &BTreeMap<ArrowKind, u32> {
+        &self.quiver
+    }
+    pub fn physics(&self) -> Option<ArrowPhysics> {
+        self.physics
+    }
+    /// Swaps the sphere type names read from the level file for the registered ones, so the
+    /// level can be saved again. Names with no sphere kind stay as they were read.
+    pub fn intern_sphere_types(&mut self, kinds: &SphereKinds) {
+        for sphere in &mut self.spheres {
+            kinds.intern(&mut sphere.sphere_type);
+        }
+        if let Some(introduces) = &mut self.introduces {
+            kinds.intern(introduces);
+        }
+    }
 }
 
 fn reset_level(mut level: ResMut<Level>) {
     *level = Level::default();
 }
 
-#[derive(Resource, Default)]
+/// The levels of the course being played
+#[derive(Resource, Clone)]
 pub struct Levels {
     levels: Vec<LevelProps>,
+    /// how many of `levels` were made by hand, the rest were generated
+    handmade: usize,
+    /// generated levels are seeded from this and their level number
+    seed: u64,
+    /// how arrows fly in levels that don't say otherwise
+    physics: ArrowPhysics,
 }
 
-#[allow(dead_code)]
-impl Levels {
-    pub fn init() -> Self {
-        let mut levels = Levels::default();
-
-        // simple first level
-        levels.insert(LevelProps::new(
-            1,
-            vec![
-                vert!(6., -5., 5.),
-                horz!(6., -6., 6.),
-                vert!(-6., -5., 5.),
-                horz!(-6., -6., 6.),
-            ],
-            vec![sphere!(Normal, 5., 0.), sphere!(Normal, 10., 0.)],
-        ));
-
-        //multiplier simple
-        levels.insert(LevelProps::new(
-            1,
-            vec![
-                vert!(8., -5., 5.),
-                horz!(6., -8., 8.),
-                vert!(-8., -5., 5.),
-                horz!(-6., -8., 8.),
-            ],
-            vec![
-                sphere!(Multiplier, 5., 0.),
-                sphere!(Normal, 10., 0.),
-                sphere!(Normal, 10., 5.),
-                sphere!(Normal, 10., -5.),
-            ],
-        ));
-
-        // multiplier advanced
-        levels.insert(LevelProps::new(
-            1,
-            vec![
-                vert!(6., -4., 4.),
-                horz!(5., -6., 6.),
-                vert!(-6., -4., 4.),
-                horz!(-5., -6., 6.),
-                vert!(0., 1., 4.),
-                vert!(0., -4., -1.),
-            ],
-            vec![
-                sphere!(Normal, -17., -6.),
-                sphere!(Multiplier, -7., 0.),
-                sphere!(Multiplier, 7., 0.),
-                sphere!(Normal, 18., 8.),
-                sphere!(Normal, 18., 0.),
-                sphere!(Normal, 18., -8.),
-            ],
-        ));
-
-        //exploder introduction
-        levels.insert(LevelProps::new(
-            3,
-            vec![
-                vert!(6., -5., 5.),
-                horz!(6., -6., 6.),
-                vert!(-6., -5., 5.),
-                horz!(-6., -6., 6.),
-            ],
-            vec![
-                sphere!(Exploder, 24., 24.),
-                sphere!(Exploder, -24., -24.),
-                sphere!(Exploder, 24., -24.),
-                sphere!(Exploder, -24., 24.),
-                sphere!(Multiplier, 0., -7.),
-                sphere!(Multiplier, 0., 7.),
-                sphere!(Normal, 0., 0.),
-                sphere!(Normal, 24., 31.),
-                sphere!(Normal, -24., -31.),
-                sphere!(Normal, -24., 31.),
-                sphere!(Normal, 24., -31.),
-                sphere!(Normal, 31., 24.),
-                sphere!(Normal, -31., -24.),
-                sphere!(Normal, -31., 24.),
-                sphere!(Normal, 31., -24.),
-                sphere!(Normal, 24., 17.),
-                sphere!(Normal, -24., -17.),
-                sphere!(Normal, 24., -17.),
-                sphere!(Normal, -24., 17.),
-            ],
-        ));
-
-        //timefreeze spiral
-        levels.insert(LevelProps::new(
-            2,
-            vec![
-                vert!(-8., -5., 6.),
-                horz!(-6., -8., 8.),
-                vert!(8., -5., 5.),
-                horz!(6., -4., 8.),
-                vert!(-4., -2., 5.),
-                horz!(-2., -3., 4.),
-                vert!(4., -1., 1.),
-                horz!(2., 0., 4.),
-            ],
-            vec![
-                sphere!(Normal, -36., 24.),
-                sphere!(TimeFreeze, -36., -24.),
-                sphere!(TimeFreeze, 36., -24.),
-                sphere!(TimeFreeze, 36., 24.),
-                sphere!(TimeFreeze, -12., 24.),
-                sphere!(TimeFreeze, -12., 0.),
-                sphere!(Multiplier, 10., 0.),
-                sphere!(Normal, 18., 0.),
-                sphere!(Normal, 18., 6.),
-                sphere!(Normal, 18., -6.),
-            ],
-        ));
-
-        levels.insert(LevelProps::new(
-            3,
-            vec![
-                //right
-                vert!(7., -4., 6.),
-                //horz left
-                horz!(5., -7., 1.),
-                //left
-                vert!(-7., -4., 4.),
-                //bottom
-                horz!(-5., -7., 7.),
-                //divider top
-                vert!(2., 4., 6.),
-                //horz top right
-                horz!(6., 3., 6.),
-                //div bot
-                vert!(2., -4., 2.),
-                WallBuilder::block(5., 1., 26., -10.),
-            ],
-            vec![
-                //left side
-                sphere!(TimeFreeze, -18., -2.),
-                sphere!(Multiplier, -18., -8.),
-                //left side array
-                sphere!(Exploder, 6., 6.),
-                sphere!(Exploder, 6., 0.),
-                sphere!(Multiplier, 3., -6.),
-                sphere!(Normal, -5., -4.),
-                sphere!(Normal, -2., -13.),
-                sphere!(Normal, 7., -15.),
-                //middle ball
-                sphere!(Normal, 5., 17.),
-                //right side
-                sphere!(Exploder, 21., 20.),
-                sphere!(Normal, 17., 14.),
-                sphere!(Normal, 17., 24.),
-                sphere!(TimeFreeze, 26., 21.),
-                //bowling
-                sphere!(Exploder, 26., -8.),
-                sphere!(Multiplier, 26., -13.),
-                sphere!(Normal, 26., -24.),
-                sphere!(Normal, 23., -24.),
-                sphere!(Normal, 20., -24.),
-                sphere!(Normal, 18., -23.),
-                sphere!(Normal, 29., -24.),
-                sphere!(Normal, 33., -24.),
-                sphere!(Normal, 35., -23.),
-            ],
-        ));
-
-        //mayhem
-        levels.insert(LevelProps::new(
-            3,
-            vec![
-                horz!(4., -7., 8.),
-                horz!(2., -7., 6.),
-                vert!(8., -5., 3.),
-                vert!(6., -3., 1.),
-                WallBuilder::block_rot(3., 6., 44.5, 20.5, FRAC_PI_4),
-                WallBuilder::block_rot(3., 6., 44.5, -26.5, -FRAC_PI_4),
-                horz!(-3., 4., 5.),
-                horz!(-5., 4., 7.),
-                horz!(-6., 0., 4.),
-                horz!(-2., 0., 4.),
-                vert!(-1., -6., -2.),
-            ],
-            vec![
-                sphere!(Bouncy, 0., 18.),
-                sphere!(Bouncy, 6., 18.),
-                sphere!(Bouncy, 12., 18.),
-                sphere!(Bouncy, 18., 18.),
-                sphere!(Bouncy, 24., 18.),
-                sphere!(Normal, 42., 0.),
-                sphere!(Bouncy, 42., 6.),
-                sphere!(Bouncy, 42., -6.),
-                sphere!(Multiplier, 42., -12.),
-                sphere!(Bouncy, 24., -24.),
-                sphere!(Bouncy, 18., -24.),
-                sphere!(Multiplier, 15., -24.),
-                sphere!(Bouncy, 12., -24.),
-                sphere!(Normal, 9., -24.),
-                sphere!(Normal, 9., -21.),
-                sphere!(Normal, 9., -27.),
-                sphere!(Normal, 9., -30.),
-                sphere!(Normal, 9., -18.),
-                sphere!(Normal, 12., -27.),
-                sphere!(Normal, 12., -21.),
-                sphere!(Normal, 12., -18.),
-                sphere!(Normal, 12., -30.),
-                sphere!(Normal, 15., -27.),
-                sphere!(Normal, 15., -21.),
-                sphere!(Normal, 15., -18.),
-                sphere!(Normal, 15., -30.),
-                sphere!(Normal, 6., -24.),
-                sphere!(Normal, 6., -21.),
-                sphere!(Normal, 6., -27.),
-                sphere!(Normal, 6., -30.),
-                sphere!(Normal, 6., -18.),
-                sphere!(Normal, 3., -24.),
-                sphere!(Normal, 3., -21.),
-                sphere!(Normal, 3., -27.),
-                sphere!(Normal, 3., -30.),
-                sphere!(Normal, 3., -18.),
-                sphere!(Normal, 0., -24.),
-                sphere!(Normal, 0., -21.),
-                sphere!(Normal, 0., -27.),
-                sphere!(Normal, 0., -30.),
-                sphere!(Normal, 0., -18.),
-            ],
-        ));
-
-        levels.insert(LevelProps::new(
-            3,
-            vec![
-                vert!(8., -5., 5.),
-                horz!(6., -8., 8.),
-                vert!(-8., -5., 5.),
-                horz!(-6., -8., 8.),
-            ],
-            vec![
-                //gravity column
-                sphere!(Gravity, -40., 0.),
-                sphere!(Normal, -40., 5.),
-                sphere!(Multiplier, -40., -5.),
-                //absorber column
-                sphere!(Absorber, -30., 0.),
-                sphere!(Multiplier, -30., 5.),
-                sphere!(Exploder, -30., 10.),
-                sphere!(Bouncy, -30., 15.),
-                //exploder testing column
-                sphere!(Exploder, -20., 0.),
-                sphere!(Multiplier, -23., 5.),
-                sphere!(Multiplier, -23., -5.),
-                sphere!(Multiplier, -17., 5.),
-                sphere!(Multiplier, -17., -5.),
-                //others
-                sphere!(TimeFreeze, -10., 0.),
-                sphere!(Normal, 0., 0.),
-                // exploder column
-                sphere!(Exploder, 10., 0.),
-                sphere!(Exploder, 10., 5.),
-                sphere!(Exploder, 10., 10.),
-                sphere!(Normal, 8., 8.),
-                // bouncy column
-                sphere!(Bouncy, 20., 0.),
-                sphere!(Multiplier, 20., 5.),
-                sphere!(Normal, 20., 10.),
-                // bouncy timefreeze column
-                sphere!(Bouncy, 30., 0.),
-                sphere!(TimeFreeze, 30., 5.),
-                sphere!(Multiplier, 30., 10.),
-                // multiplier column
-                sphere!(Multiplier, 40., 0.),
-                sphere!(Multiplier, 40., 5.),
-                sphere!(Multiplier, 40., 10.),
-                sphere!(Normal, 40., 15.),
-            ],
-        ));
-
-        levels
-    }
-
-    #[allow(dead_code)]
-    pub fn mulligan_debug() -> Self {
-        let mut levels = Levels::default();
-
-        levels.insert(LevelProps::new(
-            2,
-            vec![
-                vert!(6., -5., 5.),
-                horz!(6., -6., 6.),
-                vert!(-6., -5., 5.),
-                horz!(-6., -6., 6.),
-            ],
-            vec![sphere!(Normal, 5., 0.)],
-        ));
-        levels.insert(LevelProps::new(
-            1,
-            vec![
-                vert!(6., -5., 5.),
-                horz!(6., -6., 6.),
-                vert!(-6., -5., 5.),
-                horz!(-6., -6., 6.),
-            ],
-            vec![sphere!(Normal, -2., 0.)],
-        ));
-
-        levels
+impl Default for Levels {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            handmade: 0,
+            seed: crate::rand::random(),
+            physics: ArrowPhysics::default(),
+        }
     }
+}
 
+#[allow(dead_code)]
+impl Levels {
     fn insert(&mut self, props: LevelProps) {
         self.levels.push(props);
+        self.handmade = self.levels.len();
+    }
+    fn replace(&mut self, level: usize, props: LevelProps) {
+        if let Some(current) = self.levels.get_mut(level) {
+            *current = props;
+        }
     }
     pub fn num_levels(&self) -> usize {
         self.levels.len()
@@ -419,8 +410,29 @@ impl Levels {
         self.levels.iter()
     }
 
-    /// will get or insert a new random level based on the value
-    pub fn get(&mut self, level: usize) -> Option<&LevelProps> {
+    /// `level`, if it has been made yet
+    pub fn get(&self, level: usize) -> Option<&LevelProps> {
         self.levels.get(level)
     }
+
+    /// `level`, generating it and every level before it that hasn't been made yet
+    pub fn get_or_generate(&mut self, level: usize) -> &LevelProps {
+        while self.levels.len() <= level {
+            let index = self.levels.len();
+            let difficulty = Difficulty::ramp(index - self.handmade);
+            let props = LevelGenerator::new(self.seed.wrapping_add(index as u64), difficulty)
+                .generate()
+                .with_name(format!("Random {}", index + 1 - self.handmade));
+            self.levels.push(props);
+        }
+        &self.levels[level]
+    }
+
+    /// How arrows fly in `level`
+    pub fn physics(&self, level: usize) -> ArrowPhysics {
+        let course = self.physics;
+        self.get(level)
+            .and_then(LevelProps::physics)
+            .unwrap_or(course)
+    }
 }
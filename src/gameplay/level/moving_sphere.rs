@@ -0,0 +1,132 @@
+//! Spheres that move while a level is played.
+//!
+//! Moving spheres are kinematic bodies until they are hit or knocked. Then they become dynamic
+//! like the rest, and fly off with the speed they were moving at.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::{
+    level::{LevelState, moving_wall::path_offset},
+    sphere::{DestroySphere, KnockSphere, MarkedForDeletion},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        move_spheres
+            .in_set(PhysicsStepSet::First)
+            .run_if(in_state(LevelState::Playing)),
+    )
+    .add_systems(OnExit(LevelState::Playing), stop_spheres)
+    .add_observer(drop_on_hit)
+    .add_observer(drop_on_knock)
+    .add_observer(drop_on_mark);
+}
+
+/// How a sphere moves. Moving spheres start at their `location`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SphereMotion {
+    /// Visits `points` in order at `speed` world units a second, then goes back to the start.
+    ///
+    /// The points are in world units, from where the sphere starts.
+    Path { points: Vec<Vec2>, speed: f32 },
+    /// Circles `center` at `speed` radians a second. Negative speeds go clockwise.
+    ///
+    /// The center is in world units, from where the sphere starts.
+    Orbit { center: Vec2, speed: f32 },
+}
+
+impl SphereMotion {
+    /// How far the sphere has moved from where it started after `elapsed` seconds
+    pub fn offset(&self, elapsed: f32) -> Vec2 {
+        match self {
+            Self::Path { points, speed } => path_offset(points, speed * elapsed),
+            Self::Orbit { center, speed } => {
+                *center + Vec2::from_angle(speed * elapsed).rotate(-*center)
+            }
+        }
+    }
+}
+
+/// A sphere following its [`SphereMotion`] until it is hit
+#[derive(Component)]
+pub struct MovingSphere {
+    motion: SphereMotion,
+    start: Vec2,
+    /// seconds of physics time spent moving
+    elapsed: f32,
+}
+
+impl MovingSphere {
+    pub fn new(motion: SphereMotion, start: Vec2) -> Self {
+        Self {
+            motion,
+            start,
+            elapsed: 0.,
+        }
+    }
+}
+
+/// Sets the velocity of every moving sphere so it ends up where its motion says at the end of
+/// this physics step.
+///
+/// Only the game plane is steered, so the level's tweens are free to move the spheres in depth.
+fn move_spheres(
+    time: Res<Time>,
+    mut spheres: Query<(&mut MovingSphere, &Position, &mut LinearVelocity)>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0. {
+        return;
+    }
+    for (mut sphere, position, mut velocity) in &mut spheres {
+        sphere.elapsed += delta;
+        let target = sphere.start + sphere.motion.offset(sphere.elapsed);
+        velocity.0 = ((target - position.0.xy()) / delta).extend(0.);
+    }
+}
+
+/// Keeps moving spheres from drifting while the level flies out
+fn stop_spheres(mut spheres: Query<&mut LinearVelocity, With<MovingSphere>>) {
+    for mut velocity in &mut spheres {
+        velocity.0 = Vec3::ZERO;
+    }
+}
+
+fn drop_on_hit(
+    trigger: Trigger<DestroySphere>,
+    mut commands: Commands,
+    spheres: Query<(), With<MovingSphere>>,
+) {
+    drop_sphere(&mut commands, trigger.target(), &spheres);
+}
+
+fn drop_on_knock(
+    trigger: Trigger<KnockSphere>,
+    mut commands: Commands,
+    spheres: Query<(), With<MovingSphere>>,
+) {
+    drop_sphere(&mut commands, trigger.target(), &spheres);
+}
+
+fn drop_on_mark(
+    trigger: Trigger<OnAdd, MarkedForDeletion>,
+    mut commands: Commands,
+    spheres: Query<(), With<MovingSphere>>,
+) {
+    drop_sphere(&mut commands, trigger.target(), &spheres);
+}
+
+/// Stops `sphere` following its motion, if it has one
+fn drop_sphere(commands: &mut Commands, sphere: Entity, spheres: &Query<(), With<MovingSphere>>) {
+    if spheres.get(sphere).is_err() {
+        return;
+    }
+    // keeps its velocity, so it carries on the way it was going
+    commands
+        .entity(sphere)
+        .try_remove::<MovingSphere>()
+        .try_insert(RigidBody::Dynamic);
+}
@@ -0,0 +1,198 @@
+//! Walls that move while a level is played.
+//!
+//! Moving walls are kinematic bodies. Avian pushes spheres along with them, and arrows bounce
+//! off of them relative to how fast the wall is moving where it was hit.
+
+use std::f32::consts::{PI, TAU};
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::level::{LevelState, WallBuilder, WallKind, WallMaterial, wall_bundle};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PhysicsSchedule,
+        move_walls
+            .in_set(PhysicsStepSet::First)
+            .run_if(in_state(LevelState::Playing)),
+    )
+    .add_systems(OnExit(LevelState::Playing), stop_walls);
+}
+
+/// How a wall moves. Moving walls start where their shape puts them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WallMotion {
+    /// Visits `points` in order at `speed` world units a second, then goes back to the start.
+    ///
+    /// The points are in world units, from where the wall starts.
+    Path { points: Vec<Vec2>, speed: f32 },
+    /// Slides out to `offset` from where it starts and back, every `period` seconds
+    Oscillate { offset: Vec2, period: f32 },
+    /// Turns around its center at `speed` radians a second. Negative speeds turn clockwise.
+    Spin { speed: f32 },
+}
+
+impl WallMotion {
+    /// How far the wall has moved and turned from where it started after `elapsed` seconds
+    pub fn pose(&self, elapsed: f32) -> (Vec2, f32) {
+        match self {
+            Self::Path { points, speed } => (path_offset(points, speed * elapsed), 0.),
+            Self::Oscillate { offset, period } => {
+                if *period <= 0. {
+                    return (Vec2::ZERO, 0.);
+                }
+                // starts at rest where the wall was placed
+                let t = 0.5 - 0.5 * (TAU * elapsed / period).cos();
+                (*offset * t, 0.)
+            }
+            Self::Spin { speed } => (Vec2::ZERO, speed * elapsed),
+        }
+    }
+}
+
+/// Where a wall or sphere is after travelling `distance` along the loop from the start, through `points`
+/// and back to the start
+pub(super) fn path_offset(points: &[Vec2], distance: f32) -> Vec2 {
+    let stops = || {
+        std::iter::once(Vec2::ZERO)
+            .chain(points.iter().copied())
+            .chain(std::iter::once(Vec2::ZERO))
+    };
+    let length: f32 = stops()
+        .zip(stops().skip(1))
+        .map(|(from, to)| from.distance(to))
+        .sum();
+    if length <= f32::EPSILON {
+        return Vec2::ZERO;
+    }
+
+    let mut left = distance.rem_euclid(length);
+    for (from, to) in stops().zip(stops().skip(1)) {
+        let segment = from.distance(to);
+        if left <= segment {
+            return from.lerp(to, left / segment.max(f32::EPSILON));
+        }
+        left -= segment;
+    }
+    Vec2::ZERO
+}
+
+/// A wall that moves, as written in a level file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpawnMovingWall {
+    pub wall: WallBuilder,
+    pub motion: WallMotion,
+    #[serde(default)]
+    pub kind: WallKind,
+}
+
+/// A wall following its [`WallMotion`].
+///
+/// Unlike the other walls, each of these is its own body instead of a part of the
+/// [`Walls`](super::Walls) body.
+#[derive(Component)]
+#[require(RigidBody = RigidBody::Kinematic)]
+pub struct MovingWall {
+    motion: WallMotion,
+    start: Transform,
+    /// seconds of physics time spent moving
+    elapsed: f32,
+}
+
+/// Spawns the moving walls of a level as children of the [`Walls`](super::Walls) entity `root`
+pub(super) fn spawn_moving_walls(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: &WallMaterial,
+    root: Entity,
+    walls: &[SpawnMovingWall],
+) {
+    for moving in walls {
+        commands.spawn((
+            wall_bundle(meshes, material, &moving.wall, moving.kind),
+            MovingWall {
+                motion: moving.motion.clone(),
+                start: moving.wall.transform,
+                elapsed: 0.,
+            },
+            ChildOf(root),
+        ));
+    }
+}
+
+/// Sets the velocity of every moving wall so it ends up where its motion says at the end of
+/// this physics step.
+///
+/// The [`Walls`](super::Walls) root sits at the origin while playing, so this only steers the
+/// walls on the game plane and leaves their depth alone.
+fn move_walls(
+    time: Res<Time>,
+    mut walls: Query<(
+        &mut MovingWall,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0. {
+        return;
+    }
+    for (mut wall, position, rotation, mut linear, mut angular) in &mut walls {
+        wall.elapsed += delta;
+        let (offset, angle) = wall.motion.pose(wall.elapsed);
+
+        let target = wall.start.translation.xy() + offset;
+        linear.0 = ((target - position.0.xy()) / delta).extend(0.);
+
+        let (start_angle, _, _) = wall.start.rotation.to_euler(EulerRot::ZYX);
+        let (current_angle, _, _) = rotation.0.to_euler(EulerRot::ZYX);
+        // turn the short way around
+        let turn = (start_angle + angle - current_angle + PI).rem_euclid(TAU) - PI;
+        angular.0 = Vec3::Z * (turn / delta);
+    }
+}
+
+/// Keeps moving walls from drifting and turning while the level flies out
+fn stop_walls(mut walls: Query<(&mut LinearVelocity, &mut AngularVelocity), With<MovingWall>>) {
+    for (mut linear, mut angular) in &mut walls {
+        linear.0 = Vec3::ZERO;
+        angular.0 = Vec3::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: [Vec2; 3] = [Vec2::new(2., 0.), Vec2::new(2., 2.), Vec2::new(0., 2.)];
+
+    #[test]
+    fn path_starts_at_the_start() {
+        assert_eq!(path_offset(&SQUARE, 0.), Vec2::ZERO);
+    }
+
+    #[test]
+    fn path_goes_through_the_points_in_order() {
+        assert_eq!(path_offset(&SQUARE, 1.), Vec2::new(1., 0.));
+        assert_eq!(path_offset(&SQUARE, 2.), Vec2::new(2., 0.));
+        assert_eq!(path_offset(&SQUARE, 3.), Vec2::new(2., 1.));
+        assert_eq!(path_offset(&SQUARE, 7.), Vec2::new(0., 1.));
+    }
+
+    #[test]
+    fn path_loops_back_to_the_start() {
+        assert_eq!(path_offset(&SQUARE, 8.), Vec2::ZERO);
+        assert_eq!(path_offset(&SQUARE, 9.), Vec2::new(1., 0.));
+        assert_eq!(path_offset(&SQUARE, -1.), Vec2::new(0., 1.));
+    }
+
+    #[test]
+    fn empty_path_stays_put() {
+        assert_eq!(path_offset(&[], 5.), Vec2::ZERO);
+        assert_eq!(path_offset(&[Vec2::ZERO], 5.), Vec2::ZERO);
+    }
+}
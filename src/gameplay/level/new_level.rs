@@ -1,6 +1,6 @@
 use std::{path::Path, time::Duration};
 
-use avian3d::prelude::{CollisionLayers, PhysicsLayer, RigidBody};
+use avian3d::prelude::{CollisionLayers, RigidBody};
 use bevy::prelude::*;
 use bevy_tweening::{Animator, Tween, lens::TransformPositionLens};
 
@@ -10,10 +10,11 @@ use crate::{
         GameSet,
         gameover::GameOverState,
         level::{
-            Level, LevelState, Levels, SPHERE_START_PLANE, WALL_START_PLANE, WallMaterial,
-            WallMesh, Walls, timer::LevelSetupTimer,
+            EditorSession, Level, LevelState, Levels, SPHERE_START_PLANE, WALL_START_PLANE,
+            WallMaterial, Walls, solver::Solving, spawn_moving_walls, spawn_special_walls,
+            spawn_spheres, spawn_walls, timer::LevelSetupTimer,
         },
-        sphere::{MarkedForDeletion, MustMark, Sphere},
+        sphere::{BrokenRules, Hazard, MarkedForDeletion, MustMark, Portal, Sphere},
     },
     settings::Settings,
     third_party::avian3d::GameLayer,
@@ -41,8 +42,11 @@ pub(super) fn plugin(app: &mut App) {
         )
         .add_systems(
             Update,
-            observe_level_completion
-                .run_if(in_state(LevelState::Playing).and(in_state(GameOverState::None))),
+            observe_level_completion.run_if(
+                in_state(LevelState::Playing)
+                    .and(in_state(GameOverState::None))
+                    .and(not(resource_exists::<Solving>)),
+            ),
         );
 }
 fn init_timer(mut commands: Commands) {
@@ -64,17 +68,30 @@ impl FromWorld for LevelAssets {
     }
 }
 
+/// The spheres that still have to be broken before the level is cleared.
+///
+/// Sensor spheres count until they are gone, spheres that have to be marked count until they
+/// are marked. Portals pass arrows on and hazards shouldn't be broken, so neither counts.
+pub(super) type Unbroken = (
+    Without<Hazard>,
+    Or<(
+        (With<Sphere>, Without<MustMark>, Without<Portal>),
+        (With<MustMark>, Without<MarkedForDeletion>),
+    )>,
+);
+
 #[derive(Default)]
 struct LevelCompletion {
     timer: Option<Timer>,
 }
 fn observe_level_completion(
     mut commands: Commands,
-    sensor_balls: Query<(), (With<Sphere>, Without<MustMark>)>,
-    markable_balls: Query<(), (With<MustMark>, Without<MarkedForDeletion>)>,
+    unbroken: Query<(), Unbroken>,
     mut level: ResMut<Level>,
     mut next_state: ResMut<NextState<LevelState>>,
     mut level_completion: Local<LevelCompletion>,
+    rules: Res<BrokenRules>,
+    editor: Option<Res<EditorSession>>,
     time: Res<Time>,
     sfx: Res<LevelAssets>,
     settings: Res<Settings>,
@@ -86,8 +103,16 @@ fn observe_level_completion(
             return;
         }
 
-        level.0 += 1;
-        next_state.set(LevelState::NextLevel);
+        if editor.is_some() {
+            // play-testing from the editor, go back to editing the same level
+            next_state.set(LevelState::Editing);
+        } else if rules.failed {
+            // a rule was broken, so the same level starts over like a mulligan
+            next_state.set(LevelState::NextLevel);
+        } else {
+            level.0 += 1;
+            next_state.set(LevelState::NextLevel);
+        }
         reset_timer = true;
     }
     if reset_timer {
@@ -95,9 +120,13 @@ fn observe_level_completion(
         return;
     }
 
-    let remaining_balls_count = sensor_balls.iter().count() + markable_balls.iter().count();
+    if rules.failed {
+        // give the player a moment to see what they broke
+        level_completion.timer = Some(Timer::new(Duration::from_millis(1500), TimerMode::Once));
+        return;
+    }
 
-    if remaining_balls_count == 0 {
+    if unbroken.is_empty() {
         #[cfg(not(feature = "web"))]
         commands.trigger(RadialBackdropPulse);
         commands.spawn((
@@ -121,10 +150,7 @@ fn load_level(
     level: Res<Level>,
     timer: Res<LevelSetupTimer>,
 ) {
-    let Some(props) = levels.get(level.0) else {
-        // this should probably panic, but yknow
-        return;
-    };
+    let props = levels.get_or_generate(level.0);
 
     let tween = Tween::new(
         EaseFunction::QuadraticOut,
@@ -154,28 +180,22 @@ fn load_level(
         ))
         .id();
 
-    for wall in props.walls.iter() {
-        let collider = wall.collider.clone();
-        let mesh = match wall.mesh {
-            WallMesh::Cuboid(cuboid) => meshes.add(cuboid),
-            WallMesh::Cylinder(cylinder) => meshes.add(cylinder),
-        };
-        let material = material.0.clone();
-        commands.spawn((
-            Mesh3d(mesh),
-            collider,
-            MeshMaterial3d(material),
-            CollisionLayers::new(GameLayer::Walls, GameLayer::all_bits()),
-            wall.transform,
-            ChildOf(root),
-        ));
-    }
-    for sphere in props.spheres.iter() {
-        commands.spawn((
-            sphere.sphere_type,
-            Transform::from_xyz(sphere.location.x, sphere.location.y, SPHERE_START_PLANE),
-        ));
-    }
+    spawn_walls(&mut commands, &mut meshes, &material, root, &props.walls);
+    spawn_special_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        root,
+        &props.special_walls,
+    );
+    spawn_moving_walls(
+        &mut commands,
+        &mut meshes,
+        &material,
+        root,
+        &props.moving_walls,
+    );
+    spawn_spheres(&mut commands, &props.spheres, SPHERE_START_PLANE);
 }
 
 fn update_sphere_transform(
@@ -0,0 +1,556 @@
+//! Finds par for levels by shooting at them.
+//!
+//! The solver builds the whole game without a window or a renderer, then plays each level by
+//! firing arrows from where a bow would fire them. Arrows, spheres and walls go through the same
+//! avian3d simulation as in the game. `cargo run --release --bin solve_par` solves every level
+//! file.
+//!
+//! Every shot in a sweep of bow positions, angles and pull strengths is tried. The few shots that
+//! leave the fewest spheres are kept, and the sweep is tried again after each of them, until a
+//! sequence clears the level. A shot that hits a time freeze sphere is followed by a shot from the
+//! bow that shows up where it was, swept over angles and pull strengths only. Like in the game,
+//! that shot is not a stroke. Shots that break a hazard or break spheres out of order are thrown
+//! out. A level cleared in one shot is as short as it gets, but past that
+//! the count is the fewest shots the solver found, not a proof that there is no shorter way.
+//! Only normal arrows are fired, so the special arrows in a level's quiver are never tried.
+//! Arrows fly with the level file's own physics. Level files are solved on their own, so physics
+//! a course sets for all of its levels is not applied.
+
+use std::{
+    f32::consts::{PI, TAU},
+    fmt,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use avian3d::prelude::*;
+use bevy::{
+    asset::AssetMetaCheck,
+    prelude::*,
+    render::{RenderPlugin, settings::WgpuSettings},
+    time::TimeUpdateStrategy,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use thiserror::Error;
+
+use crate::{
+    Screen,
+    gameplay::{
+        GameState,
+        arrow::{
+            ARROW_SCALE, Arrow, ArrowAssets, ArrowPhysics, NockedOn, nock, spawn_flying_arrow,
+        },
+        level::{
+            LevelProps, LevelState, Levels, RebuildLevel,
+            loader::LevelLoaderError,
+            new_level::Unbroken,
+            validate::{level_paths, play_area, read_level},
+        },
+        sphere::{BrokenRules, SPHERE_RADIUS},
+        timefreeze::FreezeLocation,
+    },
+    world::GAME_PLANE,
+};
+
+/// The game is being played by the solver instead of a player.
+///
+/// Cleared levels stay put instead of moving on to the next level.
+#[derive(Resource)]
+pub(super) struct Solving;
+
+/// One physics step. Every update of the solver's app moves time along by exactly this much.
+const STEP: Duration = Duration::from_micros(15_625);
+
+/// how long the game gets to load its assets, in real time
+const LOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// how many steps a level gets to fly in from the start plane
+const LEVEL_START_STEPS: usize = 64 * 5;
+
+/// how many steps a shot gets before the solver moves on, a bit longer than an arrow can fly
+const SHOT_STEPS: usize = 64 * 12;
+
+/// how many steps every arrow has to sit still before the shot is over
+const STILL_STEPS: usize = 32;
+
+/// arrows slower than this are sitting still
+const STILL_SPEED: f32 = 1.;
+
+/// arrows this far outside of the play area are not coming back
+const OUT_OF_PLAY: f32 = 20.;
+
+/// how far bows are kept from walls and spheres, so arrows don't start inside of them
+const CLEARANCE: f32 = 4.;
+
+/// A shot from the bow
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shot {
+    /// where the bow is on the game plane
+    pub bow: Vec2,
+    /// which way the arrow flies, in radians counter clockwise from the right
+    pub angle: f32,
+    /// how far the string is pulled back, from 0 to 1
+    pub strength: f32,
+    /// fired from the bow a time freeze sphere hands out, which doesn't count as a stroke
+    pub frozen: bool,
+}
+
+impl Shot {
+    /// Where the arrow of this shot leaves the bow, and how fast it flies
+    fn arrow(&self, physics: &ArrowPhysics) -> (Transform, Vec3) {
+        // the bow points away from the arrow, towards the cursor the player pulls back with
+        let bow = Transform::from_translation(self.bow.extend(GAME_PLANE))
+            .with_rotation(Quat::from_rotation_z(self.angle + PI));
+        let mut arrow = Transform::from_scale(Vec3::splat(ARROW_SCALE));
+        nock(&mut arrow, &bow, self.strength);
+
+        let velocity = arrow.rotation * Vec3::new(0., physics.arrow_speed(self.strength), 0.);
+        (arrow, velocity)
+    }
+}
+
+impl fmt::Display for Shot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at ({:.1}, {:.1}), shooting at {:.0}°, pulled back {:.0}%",
+            if self.frozen { "frozen bow" } else { "bow" },
+            self.bow.x,
+            self.bow.y,
+            self.angle.to_degrees(),
+            self.strength * 100.
+        )
+    }
+}
+
+/// How finely the solver sweeps shots, and how hard it tries
+#[derive(Clone, Copy, Debug)]
+pub struct SolverSettings {
+    /// bow positions across the play area. There are as many rows as it takes to keep the
+    /// positions evenly spaced.
+    pub columns: usize,
+    /// directions to shoot in from each bow position
+    pub angles: usize,
+    /// pull strengths for each direction, from the weakest shot that counts to a full pull
+    pub strengths: usize,
+    /// the most shots a level gets before the solver gives up on it
+    pub max_shots: usize,
+    /// how many of the best shot sequences are tried again after each shot
+    pub beam: usize,
+}
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            columns: 8,
+            angles: 24,
+            strengths: 3,
+            max_shots: 5,
+            beam: 2,
+        }
+    }
+}
+
+impl SolverSettings {
+    /// Every shot to try on `props`, when arrows fly like `physics`
+    fn sweep(&self, props: &LevelProps, physics: &ArrowPhysics) -> Vec<Shot> {
+        let area = play_area();
+        let columns = self.columns.max(1);
+        let rows = ((columns as f32 * area.height() / area.width()).round() as usize).max(1);
+
+        let bows = (0..columns).flat_map(|column| {
+            (0..rows).map(move |row| {
+                area.min
+                    + area.size() * Vec2::new(column as f32 + 0.5, row as f32 + 0.5)
+                        / Vec2::new(columns as f32, rows as f32)
+            })
+        });
+        let bows = bows.filter(|bow| {
+            props
+                .all_walls()
+                .all(|wall| wall.distance(*bow) > CLEARANCE)
+                && props
+                    .spheres
+                    .iter()
+                    .all(|sphere| sphere.location.distance(*bow) > SPHERE_RADIUS + CLEARANCE)
+        });
+
+        let aims = self.aims(physics);
+        bows.flat_map(|bow| {
+            aims.iter().map(move |(angle, strength)| Shot {
+                bow,
+                angle: *angle,
+                strength: *strength,
+                frozen: false,
+            })
+        })
+        .collect()
+    }
+
+    /// Every shot to try from the bow a time freeze sphere at `location` hands out
+    fn frozen_sweep(&self, location: Vec2, physics: &ArrowPhysics) -> Vec<Shot> {
+        self.aims(physics)
+            .into_iter()
+            .map(|(angle, strength)| Shot {
+                bow: location,
+                angle,
+                strength,
+                frozen: true,
+            })
+            .collect()
+    }
+
+    /// Every angle and pull strength to try from a single bow position
+    fn aims(&self, physics: &ArrowPhysics) -> Vec<(f32, f32)> {
+        // anything weaker is canceled instead of fired
+        let weakest = (physics.min_speed / physics.arrow_speed(1.)).sqrt().min(1.);
+        let strengths: Vec<f32> = match self.strengths {
+            0 | 1 => vec![1.],
+            count => (0..count)
+                .map(|index| weakest.lerp(1., index as f32 / (count - 1) as f32))
+                .collect(),
+        };
+        let angles = self.angles.max(1);
+        (0..angles)
+            .map(|index| TAU * index as f32 / angles as f32)
+            .flat_map(|angle| strengths.iter().map(move |strength| (angle, *strength)))
+            .collect()
+    }
+}
+
+/// What the solver found for a level
+#[derive(Clone, Debug)]
+pub enum Solution {
+    /// These shots clear the level
+    Cleared(Vec<Shot>),
+    /// The shots that got the closest, and how many spheres they left
+    Stuck { shots: Vec<Shot>, remaining: usize },
+}
+
+impl Solution {
+    /// How many of the shots count towards the score
+    pub fn strokes(&self) -> usize {
+        match self {
+            Self::Cleared(shots) | Self::Stuck { shots, .. } => strokes(shots),
+        }
+    }
+}
+
+/// How many of `shots` count towards the score, frozen shots are free
+fn strokes(shots: &[Shot]) -> usize {
+    shots.iter().filter(|shot| !shot.frozen).count()
+}
+
+/// Why the solver could not play at all
+#[derive(Debug, Error)]
+pub enum SolverError {
+    #[error(transparent)]
+    CourseList(#[from] LevelLoaderError),
+    #[error("the game did not finish loading within {0:?}")]
+    Loading(Duration),
+    #[error("the level never started")]
+    LevelStart,
+}
+
+/// The par and solution of a single level file, or why it could not be read
+pub type ParReport = (String, Result<(i32, Solution), LevelLoaderError>);
+
+/// Reads the course list in `assets` and solves every level file on it once, in order.
+///
+/// Only files with one of `only` in their path are solved, unless `only` is empty.
+pub fn solve_level_files(
+    assets: &Path,
+    settings: &SolverSettings,
+    only: &[String],
+) -> Result<Vec<ParReport>, SolverError> {
+    let paths: Vec<String> = level_paths(assets)?
+        .into_iter()
+        .filter(|path| only.is_empty() || only.iter().any(|part| path.contains(part.as_str())))
+        .collect();
+
+    let mut simulation = Simulation::new(assets)?;
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let props = match read_level(assets, &path) {
+            Ok(props) => props,
+            Err(err) => {
+                reports.push((path, Err(err)));
+                continue;
+            }
+        };
+        simulation.load(&props)?;
+        let solution = simulation.solve(&props, settings);
+        reports.push((path, Ok((props.par(), solution))));
+    }
+    Ok(reports)
+}
+
+/// The whole game, without a window or a renderer, loading its assets from `assets`.
+///
+/// Nothing has run yet, so it is still up to the caller to finish and update it.
+pub(super) fn headless_game(assets: &Path) -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(AssetPlugin {
+                file_path: assets.to_string_lossy().into_owned(),
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            })
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+                ..default()
+            })
+            .disable::<WinitPlugin>(),
+    );
+    crate::add_game(&mut app);
+    app
+}
+
+/// The game, running without a window and stepped by hand
+struct Simulation {
+    app: App,
+}
+
+impl Simulation {
+    /// Builds the game and waits for it to get to the title screen
+    fn new(assets: &Path) -> Result<Self, SolverError> {
+        let mut app = headless_game(assets);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .insert_resource(Solving);
+        app.finish();
+        app.cleanup();
+
+        // assets load on other threads, so this waits in real time
+        let started = Instant::now();
+        let mut simulation = Self { app };
+        while simulation.screen() != Some(Screen::Title) {
+            if started.elapsed() > LOAD_TIMEOUT {
+                return Err(SolverError::Loading(LOAD_TIMEOUT));
+            }
+            simulation.app.update();
+        }
+        Ok(simulation)
+    }
+
+    fn screen(&self) -> Option<Screen> {
+        let state = self.app.world().get_resource::<State<Screen>>()?;
+        Some(*state.get())
+    }
+
+    /// Makes `props` the level being played, the way it starts
+    fn load(&mut self, props: &LevelProps) -> Result<(), SolverError> {
+        let mut levels = Levels::default();
+        levels.insert(props.clone());
+        self.app.world_mut().insert_resource(levels);
+
+        if self.screen() != Some(Screen::Gameplay) {
+            self.app
+                .world_mut()
+                .resource_mut::<NextState<Screen>>()
+                .set(Screen::Gameplay);
+            let started = self.step_until(LEVEL_START_STEPS, |world| {
+                world
+                    .get_resource::<State<LevelState>>()
+                    .is_some_and(|state| *state.get() == LevelState::Playing)
+            });
+            if !started {
+                return Err(SolverError::LevelStart);
+            }
+        }
+        self.reset();
+        Ok(())
+    }
+
+    /// Steps the game until `done`, or until it has taken `steps` steps.
+    ///
+    /// Returns whether it got `done`.
+    fn step_until(&mut self, steps: usize, mut done: impl FnMut(&mut World) -> bool) -> bool {
+        for _ in 0..steps {
+            self.app.update();
+            if done(self.app.world_mut()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Puts the level back the way it starts, without any arrows
+    fn reset(&mut self) {
+        self.unfreeze();
+
+        let world = self.app.world_mut();
+        let arrows: Vec<Entity> = world
+            .query_filtered::<Entity, With<Arrow>>()
+            .iter(world)
+            .collect();
+        for arrow in arrows {
+            world.despawn(arrow);
+        }
+        world.trigger(RebuildLevel);
+        self.app.update();
+    }
+
+    /// Lets time run again after a time freeze sphere was hit, like firing does in the game
+    fn unfreeze(&mut self) {
+        let world = self.app.world_mut();
+        if world
+            .get_resource::<State<GameState>>()
+            .is_some_and(|state| *state.get() != GameState::Playing)
+        {
+            // the frozen bow's arrow is replaced by the arrow of the frozen shot
+            let nocked: Vec<Entity> = world
+                .query_filtered::<Entity, With<NockedOn>>()
+                .iter(world)
+                .collect();
+            for arrow in nocked {
+                world.despawn(arrow);
+            }
+            world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::Playing);
+            self.app.update();
+        }
+    }
+
+    /// Where the bow a time freeze sphere hands out is, while time stands still
+    fn freeze_location(&self) -> Option<Vec2> {
+        let world = self.app.world();
+        let frozen = world
+            .get_resource::<State<GameState>>()
+            .is_some_and(|state| *state.get() == GameState::TimeFreeze);
+        if !frozen {
+            return None;
+        }
+        world
+            .get_resource::<FreezeLocation>()
+            .map(|freeze| freeze.location.xy())
+    }
+
+    /// Fires `shot`, and returns how many spheres are left once it is over
+    fn shoot(&mut self, shot: Shot) -> usize {
+        self.unfreeze();
+
+        let world = self.app.world_mut();
+        let scene = SceneRoot(world.resource::<ArrowAssets>().glowing.clone());
+        let physics = *world.resource::<ArrowPhysics>();
+        let (transform, velocity) = shot.arrow(&physics);
+        spawn_flying_arrow(
+            &mut world.commands(),
+            "Solver arrow",
+            transform,
+            velocity,
+            scene,
+        );
+        world.flush();
+
+        let area = play_area().inflate(OUT_OF_PLAY);
+        let mut still = 0;
+        self.step_until(SHOT_STEPS, |world| {
+            if remaining_spheres(world) == 0 {
+                return true;
+            }
+            // time stands still until the next shot
+            if world
+                .get_resource::<State<GameState>>()
+                .is_some_and(|state| *state.get() == GameState::TimeFreeze)
+            {
+                return true;
+            }
+
+            let flying = world
+                .query_filtered::<(&Position, &LinearVelocity), With<Arrow>>()
+                .iter(world)
+                .any(|(position, velocity)| {
+                    area.contains(position.xy()) && velocity.length() > STILL_SPEED
+                });
+            still = if flying { 0 } else { still + 1 };
+            still >= STILL_STEPS
+        });
+        remaining_spheres(self.app.world_mut())
+    }
+
+    /// Looks for the fewest shots that clear `props`, which has to be loaded already
+    fn solve(&mut self, props: &LevelProps, settings: &SolverSettings) -> Solution {
+        let physics = *self.app.world().resource::<ArrowPhysics>();
+        let sweep = settings.sweep(props, &physics);
+        // each sequence keeps where the time freeze sphere it hit last was, if time stands still
+        let mut best = vec![(Vec::new(), remaining_spheres(self.app.world_mut()), None)];
+        let mut cleared: Option<Vec<Shot>> = None;
+
+        loop {
+            // a sequence that can't beat the best clear found so far isn't worth another stroke
+            let most_strokes = cleared.as_ref().map_or(settings.max_shots, |shots| {
+                settings.max_shots.min(strokes(shots).saturating_sub(1))
+            });
+            let mut tried: Vec<(Vec<Shot>, usize, Option<Vec2>)> = Vec::new();
+            for (shots, _, frozen) in &best {
+                let frozen_sweep;
+                let next = match frozen {
+                    Some(location) => {
+                        frozen_sweep = settings.frozen_sweep(*location, &physics);
+                        &frozen_sweep
+                    }
+                    None if strokes(shots) < most_strokes => &sweep,
+                    None => continue,
+                };
+                for shot in next {
+                    self.reset();
+                    for earlier in shots {
+                        self.shoot(*earlier);
+                    }
+                    let remaining = self.shoot(*shot);
+                    // only clean clears count, without penalty strokes or failing the level
+                    if self.app.world().resource::<BrokenRules>().any() {
+                        continue;
+                    }
+
+                    let mut shots = shots.clone();
+                    shots.push(*shot);
+                    if remaining == 0 {
+                        if cleared
+                            .as_ref()
+                            .is_none_or(|best| strokes(&shots) < strokes(best))
+                        {
+                            cleared = Some(shots);
+                        }
+                        continue;
+                    }
+                    tried.push((shots, remaining, self.freeze_location()));
+                }
+            }
+            // frozen shots are free, so a deeper sequence may still clear it in fewer strokes
+            if let Some(best) = &cleared {
+                tried.retain(|(shots, _, _)| strokes(shots) < strokes(best));
+            }
+            if tried.is_empty() {
+                break;
+            }
+            // stable, so ties go to the shot found first
+            tried.sort_by_key(|(shots, remaining, _)| (*remaining, strokes(shots)));
+            tried.truncate(settings.beam.max(1));
+            best = tried;
+        }
+
+        if let Some(shots) = cleared {
+            return Solution::Cleared(shots);
+        }
+        let (shots, remaining, _) = best.swap_remove(0);
+        Solution::Stuck { shots, remaining }
+    }
+}
+
+/// The spheres that still have to be broken, counted like the game counts them
+fn remaining_spheres(world: &mut World) -> usize {
+    world.query_filtered::<(), Unbroken>().iter(world).count()
+}
@@ -1,31 +1,151 @@
-use avian3d::prelude::Sensor;
+use std::{borrow::Cow, fmt};
+
 use bevy::prelude::*;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, Visitor},
+    ser,
+};
+
+use crate::gameplay::{
+    level::SphereMotion,
+    sphere::{Blast, ForceField, Hazard, SphereKinds},
+};
+
+/// The name of a kind of sphere, like `Normal` or `Exploder`.
+///
+/// Each kind is registered by its own module in [`sphere`](crate::gameplay::sphere), which is
+/// also where its constant lives. Level files write the name without quotes. Names read from a
+/// level file belong to it until [`SphereKinds::intern`] swaps them for the registered ones, and
+/// only those can be written back.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SphereType(Cow<'static, str>);
+
+impl SphereType {
+    pub const fn new(name: &'static str) -> Self {
+        Self(Cow::Borrowed(name))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SphereType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for SphereType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            // written like the enum it used to be, so old level files still read
+            Cow::Borrowed(name) => serializer.serialize_unit_variant("SphereType", 0, name),
+            Cow::Owned(ref name) => Err(ser::Error::custom(format!(
+                "there is no {name} sphere type to write"
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SphereType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl Visitor<'_> for NameVisitor {
+            type Value = SphereType;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("the name of a sphere type")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<SphereType, E> {
+                Ok(SphereType(Cow::Owned(name.to_string())))
+            }
+        }
+
+        deserializer.deserialize_identifier(NameVisitor)
+    }
+}
 
-use crate::gameplay::sphere::*;
-
-#[derive(Component, Clone, Copy)]
-#[allow(dead_code)]
-pub enum SphereType {
-    Normal,
-    Multiplier,
-    TimeFreeze,
-    Exploder,
-    Bouncy,
-    Gravity,
-    Absorber,
+/// Links a portal sphere to the other portal with the same `pair`.
+///
+/// Things that fly into a portal's `facing` side come out of its pair's `facing` side.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PortalLink {
+    pub pair: u32,
+    pub facing: Vec2,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SpawnSphere {
     pub location: Vec2,
     pub sphere_type: SphereType,
+    /// only read for portal spheres
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub portal: Option<PortalLink>,
+    /// how many hits it takes to break, just one if not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hits: Option<u32>,
+    /// replaces the force field the sphere's kind comes with, or gives it one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<ForceField>,
+    /// replaces the blast of exploders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blast: Option<Blast>,
+    /// has to be broken after every sphere with a lower number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<u32>,
+    /// shouldn't be broken, and doesn't have to be to clear the level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hazard: Option<Hazard>,
+    /// moves along a path or around a point until it is hit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motion: Option<SphereMotion>,
 }
 impl SpawnSphere {
     pub fn new(location: Vec2, sphere_type: SphereType) -> Self {
         Self {
             location,
             sphere_type,
+            portal: None,
+            hits: None,
+            field: None,
+            blast: None,
+            order: None,
+            hazard: None,
+            motion: None,
         }
     }
+    pub fn with_portal(mut self, pair: u32, facing: Vec2) -> Self {
+        self.portal = Some(PortalLink { pair, facing });
+        self
+    }
+    pub fn with_hits(mut self, hits: u32) -> Self {
+        self.hits = Some(hits);
+        self
+    }
+    pub fn with_field(mut self, field: ForceField) -> Self {
+        self.field = Some(field);
+        self
+    }
+    pub fn with_blast(mut self, blast: Blast) -> Self {
+        self.blast = Some(blast);
+        self
+    }
+    pub fn with_order(mut self, order: u32) -> Self {
+        self.order = Some(order);
+        self
+    }
+    pub fn with_hazard(mut self, hazard: Hazard) -> Self {
+        self.hazard = Some(hazard);
+        self
+    }
+    pub fn with_motion(mut self, motion: SphereMotion) -> Self {
+        self.motion = Some(motion);
+        self
+    }
 }
 
 #[macro_export]
@@ -33,7 +153,7 @@ macro_rules! sphere {
     ($type:ident, $x:expr, $y:expr) => {
         $crate::gameplay::level::sphere::SpawnSphere::new(
             bevy::prelude::Vec2::new($x, $y),
-            $crate::gameplay::level::sphere::SphereType::$type,
+            $crate::gameplay::level::sphere::SphereType::new(stringify!($type)),
         )
     };
 }
@@ -42,30 +162,51 @@ pub(super) fn spawn_sphere(
     trigger: Trigger<OnAdd, SphereType>,
     mut commands: Commands,
     spheres: Query<&SphereType>,
+    kinds: Res<SphereKinds>,
 ) {
     let sphere_type = spheres.get(trigger.target()).unwrap();
-    let mut ec = commands.entity(trigger.target());
-    match sphere_type {
-        SphereType::Normal => {
-            ec.insert((Name::new("Normal Sphere"), Normal));
-        }
-        SphereType::Multiplier => {
-            ec.insert((Name::new("Multiplier Sphere"), Multiplier, Sensor));
-        }
-        SphereType::TimeFreeze => {
-            ec.insert((Name::new("TimeFreeze Sphere"), TimeFreeze, Sensor));
-        }
-        SphereType::Bouncy => {
-            ec.insert((Name::new("Bouncy Sphere"), Bouncy));
-        }
-        SphereType::Gravity => {
-            ec.insert((Name::new("Gravity Sphere"), GravitySphere));
-        }
-        SphereType::Absorber => {
-            ec.insert((Name::new("Absorber Sphere"), Absorber));
-        }
-        SphereType::Exploder => {
-            ec.insert((Name::new("Exploder Sphere"), Exploder, Sensor));
+    if !kinds.insert(sphere_type, &mut commands.entity(trigger.target())) {
+        warn!("There is no {sphere_type} sphere");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::sphere::{Normal, RegisterSphereKind, SphereKind};
+
+    #[test]
+    fn sphere_types_round_trip() {
+        for sphere_type in [SphereType::NORMAL, SphereType::TIME_FREEZE] {
+            let written = ron::to_string(&sphere_type).unwrap();
+            assert_eq!(written, sphere_type.name());
+            assert_eq!(ron::from_str::<SphereType>(&written).unwrap(), sphere_type);
         }
     }
+
+    #[test]
+    fn interned_sphere_types_write_back() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StandardMaterial>>()
+            .register_sphere_kind(SphereKind::new::<Normal>(SphereType::NORMAL));
+        let kinds = app.world().resource::<SphereKinds>();
+
+        let mut known: SphereType = ron::from_str("Normal").unwrap();
+        assert!(ron::to_string(&known).is_err());
+        assert!(kinds.intern(&mut known));
+        assert_eq!(ron::to_string(&known).unwrap(), "Normal");
+
+        let mut unknown: SphereType = ron::from_str("Nromal").unwrap();
+        assert_eq!(unknown.name(), "Nromal");
+        assert!(!kinds.intern(&mut unknown));
+        assert!(ron::to_string(&unknown).is_err());
+    }
+
+    #[test]
+    fn spheres_read_like_level_files() {
+        let sphere: SpawnSphere =
+            ron::from_str("(sphere_type: TimeFreeze, location: (5., 0.))").unwrap();
+        assert_eq!(sphere.sphere_type, SphereType::TIME_FREEZE);
+        assert_eq!(sphere.location, Vec2::new(5., 0.));
+    }
 }
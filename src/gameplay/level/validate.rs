@@ -0,0 +1,394 @@
+//! Catches broken levels before anyone has to play them.
+//!
+//! The game warns about problems when levels load. `cargo run --bin validate_levels` checks
+//! every level file without opening a window. It still builds the game, since that is where the
+//! sphere types come from.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+use crate::{
+    camera::WORLD_CAMERA_FOV,
+    gameplay::{
+        GAMEPLAY_CAMERA_OFFSET,
+        arrow::ArrowKind,
+        level::{
+            LevelProps, WallBuilder,
+            loader::{COURSE_LIST_PATH, CourseDescription, LevelLoaderError},
+            solver::headless_game,
+            sphere::SphereType,
+        },
+        sphere::{SPHERE_RADIUS, SphereKinds},
+    },
+};
+
+/// Something wrong with a level. Walls and spheres are counted from 0, in file order.
+///
+/// Special and moving walls are counted after the plain walls, where they start.
+#[derive(Debug, Error, PartialEq)]
+pub enum LevelProblem {
+    #[error("par is {0}, but it has to be at least 1")]
+    ParTooLow(i32),
+    #[error("there are no spheres an arrow can break")]
+    NothingToBreak,
+    #[error("sphere {0} overlaps sphere {1}")]
+    OverlappingSpheres(usize, usize),
+    #[error("sphere {sphere} is inside of wall {wall}")]
+    SphereInWall { sphere: usize, wall: usize },
+    #[error("sphere {0} at {1} is outside of the play area")]
+    SphereOutOfBounds(usize, Vec2),
+    #[error("wall {0} has no length")]
+    EmptyWall(usize),
+    #[error("wall {0} is a polygon with a dent in it")]
+    DentedWall(usize),
+    #[error("sphere {0} is a {1} sphere, but there is no such sphere type")]
+    UnknownSphereType(usize, SphereType),
+    #[error("sphere {0} can't be hit even once")]
+    NoHits(usize),
+    #[error("sphere {0} has a force field that doesn't reach past its center")]
+    NoFieldRadius(usize),
+    #[error("sphere {0} has a blast that doesn't reach past its center")]
+    NoBlastRadius(usize),
+    #[error("sphere {0} is a hazard with an `order`, but hazards shouldn't be broken at all")]
+    OrderedHazard(usize),
+    #[error("sphere {0} is a portal without a `portal` link")]
+    UnlinkedPortal(usize),
+    #[error("portal pair {pair} has {count} portals, but it needs exactly 2")]
+    BrokenPortalPair { pair: u32, count: usize },
+    #[error("the quiver has normal arrows, but those never run out")]
+    NormalArrowsInQuiver,
+    #[error("the quiver has no {0} arrows in it")]
+    EmptyQuiverSlot(ArrowKind),
+    #[error("arrows have to fly at least {min_speed}, but a full pull only reaches {max_speed}")]
+    UnfireableArrows { min_speed: f32, max_speed: f32 },
+    #[error("arrows fly for {0} seconds, so they are gone as soon as they are fired")]
+    NoFlightTime(f32),
+}
+
+impl LevelProps {
+    /// Everything wrong with this level, with `kinds` as the sphere types there are
+    pub fn problems(&self, kinds: &SphereKinds) -> Vec<LevelProblem> {
+        let mut problems = Vec::new();
+
+        if self.course_par < 1 {
+            problems.push(LevelProblem::ParTooLow(self.course_par));
+        }
+        for (kind, count) in &self.quiver {
+            if *kind == ArrowKind::Normal {
+                problems.push(LevelProblem::NormalArrowsInQuiver);
+            } else if *count == 0 {
+                problems.push(LevelProblem::EmptyQuiverSlot(*kind));
+            }
+        }
+        if let Some(physics) = self.physics {
+            if physics.min_speed > physics.max_speed {
+                problems.push(LevelProblem::UnfireableArrows {
+                    min_speed: physics.min_speed,
+                    max_speed: physics.max_speed,
+                });
+            }
+            if physics.flight_time <= 0. {
+                problems.push(LevelProblem::NoFlightTime(physics.flight_time));
+            }
+        }
+        // absorbers soak up arrows instead of breaking, and portals only pass them on
+        if self.spheres.iter().all(|sphere| {
+            sphere.sphere_type == SphereType::ABSORBER
+                || sphere.sphere_type == SphereType::PORTAL
+                || sphere.hazard.is_some()
+        }) {
+            problems.push(LevelProblem::NothingToBreak);
+        }
+
+        let mut pairs = BTreeMap::<u32, usize>::new();
+        for (index, sphere) in self.spheres.iter().enumerate() {
+            if sphere.sphere_type != SphereType::PORTAL {
+                continue;
+            }
+            match sphere.portal {
+                Some(link) => *pairs.entry(link.pair).or_default() += 1,
+                None => problems.push(LevelProblem::UnlinkedPortal(index)),
+            }
+        }
+        for (pair, count) in pairs {
+            if count != 2 {
+                problems.push(LevelProblem::BrokenPortalPair { pair, count });
+            }
+        }
+
+        for (index, wall) in self.all_walls().enumerate() {
+            if wall.is_empty() {
+                problems.push(LevelProblem::EmptyWall(index));
+            }
+            if !wall.is_convex() {
+                problems.push(LevelProblem::DentedWall(index));
+            }
+        }
+
+        let play_area = play_area();
+        for (index, sphere) in self.spheres.iter().enumerate() {
+            let location = sphere.location;
+
+            if !kinds.contains(&sphere.sphere_type) {
+                problems.push(LevelProblem::UnknownSphereType(
+                    index,
+                    sphere.sphere_type.clone(),
+                ));
+            }
+            if sphere.hits == Some(0) {
+                problems.push(LevelProblem::NoHits(index));
+            }
+            if sphere.field.is_some_and(|field| field.radius <= 0.) {
+                problems.push(LevelProblem::NoFieldRadius(index));
+            }
+            if sphere.blast.is_some_and(|blast| blast.radius <= 0.) {
+                problems.push(LevelProblem::NoBlastRadius(index));
+            }
+            if sphere.hazard.is_some() && sphere.order.is_some() {
+                problems.push(LevelProblem::OrderedHazard(index));
+            }
+            if !play_area.contains(location) {
+                problems.push(LevelProblem::SphereOutOfBounds(index, location));
+            }
+
+            for (other, other_sphere) in self.spheres.iter().enumerate().skip(index + 1) {
+                if location.distance(other_sphere.location) < SPHERE_RADIUS * 2. {
+                    problems.push(LevelProblem::OverlappingSpheres(index, other));
+                }
+            }
+
+            for (wall, builder) in self.all_walls().enumerate() {
+                if builder.distance(location) < SPHERE_RADIUS {
+                    problems.push(LevelProblem::SphereInWall {
+                        sphere: index,
+                        wall,
+                    });
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// The plain walls, then the special walls, then the moving walls
+    pub(super) fn all_walls(&self) -> impl Iterator<Item = &WallBuilder> {
+        self.walls
+            .iter()
+            .chain(self.special_walls.iter().map(|special| &special.wall))
+            .chain(self.moving_walls.iter().map(|moving| &moving.wall))
+    }
+}
+
+/// Where sphere centers can be while the whole sphere stays on screen.
+///
+/// This is what the gameplay camera sees of the game plane at the 16:9 size the game is made for.
+pub(super) fn play_area() -> Rect {
+    let half_height = GAMEPLAY_CAMERA_OFFSET * (WORLD_CAMERA_FOV * 0.5).tan();
+    let half_size = Vec2::new(half_height * 16. / 9., half_height) - SPHERE_RADIUS;
+    Rect::from_center_half_size(Vec2::ZERO, half_size)
+}
+
+/// The problems of a single level file, or why it could not be read
+pub type LevelFileReport = (String, Result<Vec<LevelProblem>, LevelLoaderError>);
+
+/// Reads the course list in `assets` and checks every level file on it once, in order
+pub fn validate_level_files(assets: &Path) -> Result<Vec<LevelFileReport>, LevelLoaderError> {
+    let paths = level_paths(assets)?;
+    let game = headless_game(assets);
+    let kinds = game.world().resource::<SphereKinds>();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let problems = read_level(assets, &path).map(|props| props.problems(kinds));
+            (path, problems)
+        })
+        .collect())
+}
+
+/// Every level file on the course list in `assets`, once each, in the order they are listed
+pub(super) fn level_paths(assets: &Path) -> Result<Vec<String>, LevelLoaderError> {
+    let list = fs::read(assets.join(COURSE_LIST_PATH))?;
+    let courses: Vec<CourseDescription> = ron::de::from_bytes(&list)?;
+
+    let mut paths: Vec<String> = Vec::new();
+    for path in courses.into_iter().flat_map(|course| course.levels) {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Reads the level file at `path`, relative to `assets`
+pub(super) fn read_level(assets: &Path, path: &str) -> Result<LevelProps, LevelLoaderError> {
+    let bytes = fs::read(assets.join(path))?;
+    Ok(ron::de::from_bytes(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gameplay::{
+        arrow::ArrowPhysics,
+        sphere::{Blast, ForceField, Hazard, Normal, Portal, RegisterSphereKind, SphereKind},
+    };
+
+    /// The sphere types the test levels use
+    fn kinds() -> SphereKinds {
+        let mut app = App::new();
+        app.init_resource::<Assets<StandardMaterial>>()
+            .register_sphere_kind(SphereKind::new::<Normal>(SphereType::NORMAL))
+            .register_sphere_kind(SphereKind::new::<Portal>(SphereType::PORTAL));
+        app.world_mut().remove_resource::<SphereKinds>().unwrap()
+    }
+
+    /// One normal sphere in the middle, with a wall out of its way
+    fn clean_level() -> LevelProps {
+        LevelProps::new(
+            1,
+            vec![WallBuilder::block(2., 2., 8., 0.)],
+            vec![sphere!(Normal, 0., 0.)],
+        )
+    }
+
+    /// The clean level after `edit`, which breaks it in exactly one way
+    fn broken(edit: impl FnOnce(&mut LevelProps)) -> LevelProps {
+        let mut props = clean_level();
+        edit(&mut props);
+        props
+    }
+
+    #[test]
+    fn clean_level_has_no_problems() {
+        let problems = clean_level().problems(&kinds());
+        assert!(problems.is_empty(), "{problems:?}");
+    }
+
+    #[test]
+    fn each_problem_is_reported() {
+        let cases = [
+            (
+                broken(|props| props.course_par = 0),
+                LevelProblem::ParTooLow(0),
+            ),
+            (
+                broken(|props| props.spheres.clear()),
+                LevelProblem::NothingToBreak,
+            ),
+            (
+                broken(|props| props.spheres.push(sphere!(Normal, 0.5, 0.))),
+                LevelProblem::OverlappingSpheres(0, 1),
+            ),
+            (
+                broken(|props| props.spheres.push(sphere!(Normal, 8., 0.))),
+                LevelProblem::SphereInWall { sphere: 1, wall: 0 },
+            ),
+            (
+                broken(|props| props.spheres.push(sphere!(Normal, 1000., 0.))),
+                LevelProblem::SphereOutOfBounds(1, Vec2::new(1000., 0.)),
+            ),
+            (
+                broken(|props| props.walls.push(WallBuilder::block(0., 2., -8., 0.))),
+                LevelProblem::EmptyWall(1),
+            ),
+            (
+                broken(|props| {
+                    props.walls.push(WallBuilder::polygon(vec![
+                        Vec2::new(-10., -1.),
+                        Vec2::new(-6., -1.),
+                        Vec2::new(-8., 0.),
+                        Vec2::new(-6., 1.),
+                        Vec2::new(-10., 1.),
+                    ]))
+                }),
+                LevelProblem::DentedWall(1),
+            ),
+            (
+                broken(|props| props.spheres.push(sphere!(Portal, -5., 0.))),
+                LevelProblem::UnlinkedPortal(1),
+            ),
+            (
+                broken(|props| {
+                    let portal = sphere!(Portal, -5., 0.).with_portal(3, Vec2::X);
+                    props.spheres.push(portal);
+                }),
+                LevelProblem::BrokenPortalPair { pair: 3, count: 1 },
+            ),
+            (
+                broken(|props| props.spheres[0].hits = Some(0)),
+                LevelProblem::NoHits(0),
+            ),
+            (
+                broken(|props| {
+                    props.spheres[0].field = Some(ForceField {
+                        radius: 0.,
+                        ..default()
+                    })
+                }),
+                LevelProblem::NoFieldRadius(0),
+            ),
+            (
+                broken(|props| {
+                    props.spheres[0].blast = Some(Blast {
+                        radius: 0.,
+                        ..default()
+                    })
+                }),
+                LevelProblem::NoBlastRadius(0),
+            ),
+            (
+                broken(|props| {
+                    let hazard = sphere!(Normal, -5., 0.)
+                        .with_hazard(Hazard::Fail)
+                        .with_order(1);
+                    props.spheres.push(hazard);
+                }),
+                LevelProblem::OrderedHazard(1),
+            ),
+            (
+                broken(|props| {
+                    props.quiver.insert(ArrowKind::Normal, 2);
+                }),
+                LevelProblem::NormalArrowsInQuiver,
+            ),
+            (
+                broken(|props| {
+                    props.quiver.insert(ArrowKind::Piercing, 0);
+                }),
+                LevelProblem::EmptyQuiverSlot(ArrowKind::Piercing),
+            ),
+            (
+                broken(|props| {
+                    props.physics = Some(ArrowPhysics {
+                        min_speed: 80.,
+                        max_speed: 60.,
+                        ..default()
+                    })
+                }),
+                LevelProblem::UnfireableArrows {
+                    min_speed: 80.,
+                    max_speed: 60.,
+                },
+            ),
+            (
+                broken(|props| {
+                    props.physics = Some(ArrowPhysics {
+                        flight_time: 0.,
+                        ..default()
+                    })
+                }),
+                LevelProblem::NoFlightTime(0.),
+            ),
+            (
+                broken(|props| props.spheres.push(sphere!(Nromal, -5., 0.))),
+                LevelProblem::UnknownSphereType(1, SphereType::new("Nromal")),
+            ),
+        ];
+        let kinds = kinds();
+        for (props, problem) in cases {
+            assert_eq!(props.problems(&kinds), [problem]);
+        }
+    }
+}
@@ -1,16 +1,226 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
 use avian3d::prelude::Collider;
-use bevy::prelude::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
+use serde::{Deserialize, Serialize};
 
 use crate::world::BLOCK_LEN;
 
+/// The most an arc turns in a single one of its pieces
+const ARC_STEP: f32 = PI / 16.;
+
 #[allow(dead_code)]
+#[derive(Component, Clone)]
 pub enum WallMesh {
     Cuboid(Cuboid),
     Cylinder(Extrusion<Circle>),
+    /// Convex outlines on the game plane, counter clockwise, extruded to the depth of a wall
+    Prism(Vec<Vec<Vec2>>),
+}
+
+impl WallMesh {
+    /// How far `local` is from the edge of the wall, in the wall's own space.
+    /// Zero when it is inside.
+    pub fn local_distance(&self, local: Vec2) -> f32 {
+        match self {
+            Self::Cuboid(cuboid) => (local.abs() - cuboid.half_size.xy())
+                .max(Vec2::ZERO)
+                .length(),
+            Self::Cylinder(cylinder) => (local.length() - cylinder.base_shape.radius).max(0.),
+            Self::Prism(parts) => parts
+                .iter()
+                .map(|outline| outline_distance(outline, local))
+                .fold(f32::INFINITY, f32::min),
+        }
+    }
+
+    /// The smallest rectangle around the wall, in the wall's own space
+    pub fn local_bounds(&self) -> Rect {
+        match self {
+            Self::Cuboid(cuboid) => Rect::from_center_half_size(Vec2::ZERO, cuboid.half_size.xy()),
+            Self::Cylinder(cylinder) => {
+                Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(cylinder.base_shape.radius))
+            }
+            Self::Prism(parts) => parts
+                .iter()
+                .flatten()
+                .fold(Rect::EMPTY, |bounds, point| bounds.union_point(*point)),
+        }
+    }
+}
+
+/// How far `point` is from a convex, counter clockwise `outline`. Zero when it is inside.
+fn outline_distance(outline: &[Vec2], point: Vec2) -> f32 {
+    let edges = || outline.iter().zip(outline.iter().cycle().skip(1));
+    if outline.len() >= 3 && edges().all(|(a, b)| (*b - *a).perp_dot(point - *a) >= 0.) {
+        return 0.;
+    }
+    edges()
+        .map(|(a, b)| {
+            let edge = *b - *a;
+            let t =
+                ((point - *a).dot(edge) / edge.length_squared().max(f32::EPSILON)).clamp(0., 1.);
+            point.distance(*a + edge * t)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Twice the signed area of `outline`. Positive when it goes counter clockwise.
+fn signed_area(outline: &[Vec2]) -> f32 {
+    outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum()
+}
+
+/// Whether `outline` has no dents, going either way around
+fn is_convex(outline: &[Vec2]) -> bool {
+    let len = outline.len();
+    if len < 3 {
+        return false;
+    }
+    let turns = (0..len).map(|index| {
+        let a = outline[index];
+        let b = outline[(index + 1) % len];
+        let c = outline[(index + 2) % len];
+        (b - a).perp_dot(c - b)
+    });
+    let (mut left, mut right) = (false, false);
+    for turn in turns {
+        left |= turn > f32::EPSILON;
+        right |= turn < -f32::EPSILON;
+    }
+    !(left && right)
+}
+
+/// A mesh of every outline in `parts` extruded to the depth of a wall
+fn prism_mesh(parts: &[Vec<Vec2>]) -> Mesh {
+    let half_depth = BLOCK_LEN * 0.5;
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+
+    let mut vertex = |point: Vec2, z: f32, normal: Vec3| {
+        positions.push([point.x, point.y, z]);
+        normals.push(normal.to_array());
+        uvs.push([point.x / BLOCK_LEN, point.y / BLOCK_LEN]);
+        positions.len() as u32 - 1
+    };
+
+    for outline in parts {
+        if outline.len() < 3 {
+            continue;
+        }
+        // front and back faces, fanned out from the first point
+        for (z, normal) in [(half_depth, Vec3::Z), (-half_depth, Vec3::NEG_Z)] {
+            let face: Vec<u32> = outline
+                .iter()
+                .map(|point| vertex(*point, z, normal))
+                .collect();
+            for index in 1..face.len() - 1 {
+                if normal == Vec3::Z {
+                    indices.extend([face[0], face[index], face[index + 1]]);
+                } else {
+                    indices.extend([face[0], face[index + 1], face[index]]);
+                }
+            }
+        }
+        // the sides, with their own vertices so the edges stay sharp
+        for (a, b) in outline.iter().zip(outline.iter().cycle().skip(1)) {
+            // outlines go counter clockwise, so the outside is on the right
+            let normal = -(*b - *a).perp().normalize_or_zero().extend(0.);
+            let back_a = vertex(*a, -half_depth, normal);
+            let back_b = vertex(*b, -half_depth, normal);
+            let front_b = vertex(*b, half_depth, normal);
+            let front_a = vertex(*a, half_depth, normal);
+            indices.extend([back_a, back_b, front_b, back_a, front_b, front_a]);
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// The serializable description of a wall, as written in a level file.
+///
+/// `Horz` and `Vert` are in block units, exactly like [`horz!`] and [`vert!`].
+/// Everything else mirrors the matching [`WallBuilder`] constructor.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WallShape {
+    Horz(f32, f32, f32),
+    Vert(f32, f32, f32),
+    Horizontal {
+        y: f32,
+        start_x: f32,
+        end_x: f32,
+    },
+    Vertical {
+        x: f32,
+        start_y: f32,
+        end_y: f32,
+    },
+    Pole {
+        radius: f32,
+        x: f32,
+        y: f32,
+    },
+    Block {
+        x_len: f32,
+        y_len: f32,
+        x: f32,
+        y: f32,
+    },
+    BlockRot {
+        x_len: f32,
+        y_len: f32,
+        x: f32,
+        y: f32,
+        rot: f32,
+    },
+    /// A convex outline, in world units
+    Polygon {
+        points: Vec<Vec2>,
+    },
+    /// A right triangle filling half of a block, with the square corner at its bottom left
+    /// before it is turned by `rot`
+    Wedge {
+        x_len: f32,
+        y_len: f32,
+        x: f32,
+        y: f32,
+        rot: f32,
+    },
+    /// A curved wall `thickness` wide, `radius` from `(x, y)` to its middle.
+    ///
+    /// It goes counter clockwise from the `start` angle to the `end` angle, in radians. Ends a
+    /// whole number of turns apart make a full ring.
+    Arc {
+        x: f32,
+        y: f32,
+        radius: f32,
+        thickness: f32,
+        start: f32,
+        end: f32,
+    },
 }
 
 // this is a builder.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "WallShape", into = "WallShape")]
 pub struct WallBuilder {
+    pub shape: WallShape,
     pub mesh: WallMesh,
     pub collider: Collider,
     pub transform: Transform, //start:
@@ -29,6 +239,7 @@ impl WallBuilder {
 
         let transform = Transform::from_xyz(middle_x, y, 0.);
         Self {
+            shape: WallShape::Horizontal { y, start_x, end_x },
             mesh: WallMesh::Cuboid(mesh),
             collider,
             transform,
@@ -45,6 +256,7 @@ impl WallBuilder {
 
         let transform = Transform::from_xyz(x, middle_y, 0.);
         Self {
+            shape: WallShape::Vertical { x, start_y, end_y },
             mesh: WallMesh::Cuboid(mesh),
             collider,
             transform,
@@ -53,10 +265,16 @@ impl WallBuilder {
     pub fn pole(radius: f32, x: f32, y: f32) -> Self {
         let mesh = Extrusion::new(Circle::new(radius), BLOCK_LEN);
 
-        let collider = Collider::cylinder(radius, 1.);
+        // cylinder colliders stand along y, but the mesh is extruded along z
+        let collider = Collider::compound(vec![(
+            Vec3::ZERO,
+            Quat::from_rotation_x(FRAC_PI_2),
+            Collider::cylinder(radius, BLOCK_LEN),
+        )]);
 
         let transform = Transform::from_xyz(x, y, 0.);
         Self {
+            shape: WallShape::Pole { radius, x, y },
             mesh: WallMesh::Cylinder(mesh),
             collider,
             transform,
@@ -69,6 +287,7 @@ impl WallBuilder {
 
         let transform = Transform::from_xyz(x, y, 0.);
         Self {
+            shape: WallShape::Block { x_len, y_len, x, y },
             mesh: WallMesh::Cuboid(mesh),
             collider,
             transform,
@@ -81,22 +300,360 @@ impl WallBuilder {
 
         let transform = Transform::from_xyz(x, y, 0.).with_rotation(Quat::from_rotation_z(rot));
         Self {
+            shape: WallShape::BlockRot {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            },
             mesh: WallMesh::Cuboid(mesh),
             collider,
             transform,
         }
     }
+
+    /// A wall shaped like the convex outline `points`, in world units
+    pub fn polygon(points: Vec<Vec2>) -> Self {
+        let center = points.iter().sum::<Vec2>() / points.len().max(1) as f32;
+        let outline = points.iter().map(|point| *point - center).collect();
+        Self::prism(
+            WallShape::Polygon { points },
+            vec![outline],
+            Transform::from_xyz(center.x, center.y, 0.),
+        )
+    }
+    pub fn wedge(x_len: f32, y_len: f32, x: f32, y: f32, rot: f32) -> Self {
+        let half = Vec2::new(x_len, y_len) * 0.5;
+        let outline = vec![
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(half.x, -half.y),
+            Vec2::new(-half.x, half.y),
+        ];
+        Self::prism(
+            WallShape::Wedge {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            },
+            vec![outline],
+            Transform::from_xyz(x, y, 0.).with_rotation(Quat::from_rotation_z(rot)),
+        )
+    }
+    pub fn arc(x: f32, y: f32, radius: f32, thickness: f32, start: f32, end: f32) -> Self {
+        let inner = (radius - thickness * 0.5).max(0.);
+        let outer = radius + thickness * 0.5;
+        let sweep = (end - start).rem_euclid(TAU);
+        // ends a whole number of turns apart make a ring
+        let sweep = if sweep <= f32::EPSILON && start != end {
+            TAU
+        } else {
+            sweep
+        };
+        let (from, to) = (start, start + sweep);
+        // arcs are bent, so they are made of pieces that are not
+        let pieces = ((to - from) / ARC_STEP).ceil().max(1.) as usize;
+        let parts = (0..pieces)
+            .map(|piece| {
+                let a = Vec2::from_angle(from + (to - from) * piece as f32 / pieces as f32);
+                let b = Vec2::from_angle(from + (to - from) * (piece + 1) as f32 / pieces as f32);
+                vec![a * inner, a * outer, b * outer, b * inner]
+            })
+            .collect();
+        Self::prism(
+            WallShape::Arc {
+                x,
+                y,
+                radius,
+                thickness,
+                start,
+                end,
+            },
+            parts,
+            Transform::from_xyz(x, y, 0.),
+        )
+    }
+    /// Extrudes convex outlines around `transform` to the depth of a wall
+    fn prism(shape: WallShape, mut parts: Vec<Vec<Vec2>>, transform: Transform) -> Self {
+        let half_depth = BLOCK_LEN * 0.5;
+        for outline in &mut parts {
+            if signed_area(outline) < 0. {
+                outline.reverse();
+            }
+        }
+        let hulls: Vec<_> = parts
+            .iter()
+            .filter_map(|outline| {
+                let points = outline
+                    .iter()
+                    .flat_map(|point| [point.extend(half_depth), point.extend(-half_depth)])
+                    .collect();
+                Collider::convex_hull(points)
+            })
+            .map(|hull| (Vec3::ZERO, Quat::IDENTITY, hull))
+            .collect();
+        // compounds can't be empty, so a flat outline gets a speck to collide with instead.
+        // `validate_levels` points these out.
+        let collider = if hulls.is_empty() {
+            Collider::sphere(f32::EPSILON)
+        } else {
+            Collider::compound(hulls)
+        };
+        Self {
+            shape,
+            mesh: WallMesh::Prism(parts),
+            collider,
+            transform,
+        }
+    }
+    /// The mesh of this wall, ready to be added to the mesh assets
+    pub fn build_mesh(&self) -> Mesh {
+        match &self.mesh {
+            WallMesh::Cuboid(cuboid) => Mesh::from(*cuboid),
+            WallMesh::Cylinder(cylinder) => Mesh::from(*cylinder),
+            WallMesh::Prism(parts) => prism_mesh(parts),
+        }
+    }
+}
+
+impl WallShape {
+    /// Moves the shape by `delta` world units
+    pub fn translated(self, delta: Vec2) -> Self {
+        let blocks = delta / BLOCK_LEN;
+        match self {
+            Self::Horz(y, start_x, end_x) => {
+                Self::Horz(y + blocks.y, start_x + blocks.x, end_x + blocks.x)
+            }
+            Self::Vert(x, start_y, end_y) => {
+                Self::Vert(x + blocks.x, start_y + blocks.y, end_y + blocks.y)
+            }
+            Self::Horizontal { y, start_x, end_x } => Self::Horizontal {
+                y: y + delta.y,
+                start_x: start_x + delta.x,
+                end_x: end_x + delta.x,
+            },
+            Self::Vertical { x, start_y, end_y } => Self::Vertical {
+                x: x + delta.x,
+                start_y: start_y + delta.y,
+                end_y: end_y + delta.y,
+            },
+            Self::Pole { radius, x, y } => Self::Pole {
+                radius,
+                x: x + delta.x,
+                y: y + delta.y,
+            },
+            Self::Block { x_len, y_len, x, y } => Self::Block {
+                x_len,
+                y_len,
+                x: x + delta.x,
+                y: y + delta.y,
+            },
+            Self::BlockRot {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            } => Self::BlockRot {
+                x_len,
+                y_len,
+                x: x + delta.x,
+                y: y + delta.y,
+                rot,
+            },
+            Self::Polygon { points } => Self::Polygon {
+                points: points.into_iter().map(|point| point + delta).collect(),
+            },
+            Self::Wedge {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            } => Self::Wedge {
+                x_len,
+                y_len,
+                x: x + delta.x,
+                y: y + delta.y,
+                rot,
+            },
+            Self::Arc {
+                x,
+                y,
+                radius,
+                thickness,
+                start,
+                end,
+            } => Self::Arc {
+                x: x + delta.x,
+                y: y + delta.y,
+                radius,
+                thickness,
+                start,
+                end,
+            },
+        }
+    }
+}
+
+impl WallBuilder {
+    /// The shape of this wall rotated by `angle` radians around its center.
+    ///
+    /// Poles are round, so they are returned as they are. Arcs turn around the center of their
+    /// circle.
+    pub fn rotated(&self, angle: f32) -> WallShape {
+        match self.shape.clone() {
+            WallShape::Polygon { points } => {
+                let center = self.transform.translation.xy();
+                let rotation = Vec2::from_angle(angle);
+                return WallShape::Polygon {
+                    points: points
+                        .into_iter()
+                        .map(|point| center + rotation.rotate(point - center))
+                        .collect(),
+                };
+            }
+            WallShape::Wedge {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            } => {
+                return WallShape::Wedge {
+                    x_len,
+                    y_len,
+                    x,
+                    y,
+                    rot: rot + angle,
+                };
+            }
+            WallShape::Arc {
+                x,
+                y,
+                radius,
+                thickness,
+                start,
+                end,
+            } => {
+                return WallShape::Arc {
+                    x,
+                    y,
+                    radius,
+                    thickness,
+                    start: start + angle,
+                    end: end + angle,
+                };
+            }
+            _ => {}
+        }
+        let WallMesh::Cuboid(cuboid) = self.mesh else {
+            return self.shape.clone();
+        };
+        let (rot, _, _) = self.transform.rotation.to_euler(EulerRot::ZYX);
+        WallShape::BlockRot {
+            x_len: cuboid.half_size.x * 2.,
+            y_len: cuboid.half_size.y * 2.,
+            x: self.transform.translation.x,
+            y: self.transform.translation.y,
+            rot: rot + angle,
+        }
+    }
+
+    /// Whether `point` on the game plane is inside of this wall
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.distance(point) <= 0.
+    }
+
+    /// How far `point` on the game plane is from the edge of this wall. Zero when it is inside.
+    pub fn distance(&self, point: Vec2) -> f32 {
+        let local = (self.transform.rotation.inverse()
+            * (point.extend(0.) - self.transform.translation.with_z(0.)))
+        .xy();
+        self.mesh.local_distance(local)
+    }
+
+    /// Whether the wall is too thin to be seen or hit
+    pub fn is_empty(&self) -> bool {
+        match &self.mesh {
+            WallMesh::Cuboid(cuboid) => cuboid.half_size.xy().min_element() <= f32::EPSILON,
+            WallMesh::Cylinder(cylinder) => cylinder.base_shape.radius <= f32::EPSILON,
+            WallMesh::Prism(parts) => parts
+                .iter()
+                .all(|outline| signed_area(outline).abs() <= f32::EPSILON),
+        }
+    }
+
+    /// Whether the outline of a polygon wall has no dents. Every other shape always does.
+    pub fn is_convex(&self) -> bool {
+        match &self.shape {
+            WallShape::Polygon { points } => is_convex(points),
+            _ => true,
+        }
+    }
+}
+
+impl From<WallShape> for WallBuilder {
+    fn from(shape: WallShape) -> Self {
+        let mut wall = match shape {
+            WallShape::Horz(y, start_x, end_x) => Self::horizontal(
+                BLOCK_LEN * y,
+                BLOCK_LEN * start_x - BLOCK_LEN * 0.5,
+                BLOCK_LEN * end_x + BLOCK_LEN * 0.5,
+            ),
+            WallShape::Vert(x, start_y, end_y) => Self::vertical(
+                BLOCK_LEN * x,
+                BLOCK_LEN * start_y - BLOCK_LEN * 0.5,
+                BLOCK_LEN * end_y + BLOCK_LEN * 0.5,
+            ),
+            WallShape::Horizontal { y, start_x, end_x } => Self::horizontal(y, start_x, end_x),
+            WallShape::Vertical { x, start_y, end_y } => Self::vertical(x, start_y, end_y),
+            WallShape::Pole { radius, x, y } => Self::pole(radius, x, y),
+            WallShape::Block { x_len, y_len, x, y } => Self::block(x_len, y_len, x, y),
+            WallShape::BlockRot {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            } => Self::block_rot(x_len, y_len, x, y, rot),
+            WallShape::Polygon { ref points } => Self::polygon(points.clone()),
+            WallShape::Wedge {
+                x_len,
+                y_len,
+                x,
+                y,
+                rot,
+            } => Self::wedge(x_len, y_len, x, y, rot),
+            WallShape::Arc {
+                x,
+                y,
+                radius,
+                thickness,
+                start,
+                end,
+            } => Self::arc(x, y, radius, thickness, start, end),
+        };
+        // keep the block units around so the wall is saved the way it was written
+        wall.shape = shape;
+        wall
+    }
+}
+
+impl From<WallBuilder> for WallShape {
+    fn from(wall: WallBuilder) -> Self {
+        wall.shape
+    }
 }
 
 /// Values are automatically multiplied by block len
 #[macro_export]
 macro_rules! vert {
     ($x:literal, $start_y:literal, $end_y:literal) => {
-        #[allow(clippy::neg_multiply)]
-        $crate::gameplay::level::wall::WallBuilder::vertical(
-            $crate::world::BLOCK_LEN * $x,
-            $crate::world::BLOCK_LEN * $start_y - $crate::world::BLOCK_LEN * 0.5,
-            $crate::world::BLOCK_LEN * $end_y + $crate::world::BLOCK_LEN * 0.5,
+        $crate::gameplay::level::wall::WallBuilder::from(
+            $crate::gameplay::level::wall::WallShape::Vert($x, $start_y, $end_y),
         )
     };
 }
@@ -104,11 +661,8 @@ macro_rules! vert {
 #[macro_export]
 macro_rules! horz {
     ($x:literal, $start_y:literal, $end_y:literal) => {
-        #[allow(clippy::neg_multiply)]
-        $crate::gameplay::level::wall::WallBuilder::horizontal(
-            $crate::world::BLOCK_LEN * $x,
-            $crate::world::BLOCK_LEN * $start_y - $crate::world::BLOCK_LEN * 0.5,
-            $crate::world::BLOCK_LEN * $end_y + $crate::world::BLOCK_LEN * 0.5,
+        $crate::gameplay::level::wall::WallBuilder::from(
+            $crate::gameplay::level::wall::WallShape::Horz($x, $start_y, $end_y),
         )
     };
 }
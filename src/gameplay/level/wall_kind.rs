@@ -0,0 +1,136 @@
+//! Walls made of something other than plain wall.
+//!
+//! How arrows bounce off of each kind lives in [`arrow`](crate::gameplay::arrow).
+
+use bevy::{
+    color::palettes::tailwind::{AMBER_800, LIME_400, SKY_300, STONE_400},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gameplay::{
+        level::{WallBuilder, WallMaterial, WallMesh, wall_bundle},
+        sphere::GibMeshes,
+    },
+    world::BLOCK_LEN,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(insert_wall_hits).add_observer(hit_wall);
+}
+
+/// What a wall is made of, which changes what happens to arrows that hit it
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum WallKind {
+    #[default]
+    Plain,
+    /// Arrows bounce off at full speed, and it doesn't count towards their bounce limit
+    Bouncy,
+    /// Arrows stop dead
+    Sticky,
+    /// Shatters after `hits` arrows bounce off of it
+    Breakable { hits: u32 },
+    /// Arrows bounce off of the side `facing` points out of, and fly through from the other side.
+    ///
+    /// `facing` turns with the wall. Spheres are blocked from both sides.
+    OneWay { facing: Vec2 },
+}
+
+impl WallKind {
+    pub(super) fn color(&self) -> Color {
+        match self {
+            Self::Plain => Color::WHITE,
+            Self::Bouncy => LIME_400.into(),
+            Self::Sticky => AMBER_800.into(),
+            Self::Breakable { .. } => STONE_400.into(),
+            Self::OneWay { .. } => SKY_300.with_alpha(0.6).into(),
+        }
+    }
+}
+
+/// A wall made of something other than plain wall, as written in a level file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpawnSpecialWall {
+    pub wall: WallBuilder,
+    pub kind: WallKind,
+}
+
+/// Spawns the special walls of a level as children of the [`Walls`](super::Walls) entity `root`
+pub(super) fn spawn_special_walls(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: &WallMaterial,
+    root: Entity,
+    walls: &[SpawnSpecialWall],
+) {
+    for special in walls {
+        commands.spawn((
+            wall_bundle(meshes, material, &special.wall, special.kind),
+            ChildOf(root),
+        ));
+    }
+}
+
+/// How many more arrows a breakable wall can take
+#[derive(Component)]
+pub struct WallHits(u32);
+
+fn insert_wall_hits(
+    trigger: Trigger<OnAdd, WallKind>,
+    mut commands: Commands,
+    kinds: Query<&WallKind>,
+) {
+    let Ok(WallKind::Breakable { hits }) = kinds.get(trigger.target()) else {
+        return;
+    };
+    commands.entity(trigger.target()).insert(WallHits(*hits));
+}
+
+/// An arrow bounced off of this wall
+#[derive(Event)]
+pub struct HitWall;
+
+fn hit_wall(
+    trigger: Trigger<HitWall>,
+    mut commands: Commands,
+    mut walls: Query<(
+        &mut WallHits,
+        &WallMesh,
+        &GlobalTransform,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+    gibs: Res<GibMeshes>,
+) {
+    let Ok((mut hits, mesh, transform, material)) = walls.get_mut(trigger.target()) else {
+        return;
+    };
+    hits.0 = hits.0.saturating_sub(1);
+    if hits.0 > 0 {
+        return;
+    }
+
+    // shatter like spheres laid over the wall
+    let bounds = mesh.local_bounds();
+    let step = BLOCK_LEN * 0.5;
+    let columns = (bounds.width() / step).ceil().max(1.) as usize;
+    let rows = (bounds.height() / step).ceil().max(1.) as usize;
+    let mut shattered = false;
+    for column in 0..columns {
+        for row in 0..rows {
+            let local = bounds.min
+                + bounds.size() * Vec2::new(column as f32 + 0.5, row as f32 + 0.5)
+                    / Vec2::new(columns as f32, rows as f32);
+            if mesh.local_distance(local) > 0. {
+                continue;
+            }
+            let at = transform.transform_point(local.extend(0.));
+            gibs.spawn_gibs(&mut commands, at, &material.0);
+            shattered = true;
+        }
+    }
+    if !shattered {
+        gibs.spawn_gibs(&mut commands, transform.translation(), &material.0);
+    }
+    commands.entity(trigger.target()).try_despawn();
+}
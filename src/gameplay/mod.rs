@@ -5,11 +5,14 @@ pub mod arrow;
 mod audio;
 pub mod bow;
 pub mod cursor;
+mod gamepad;
 pub mod level;
 mod mulligan;
+mod quiver;
 mod scorecard;
 pub mod sphere;
 pub mod timefreeze;
+mod touch;
 
 mod pause;
 mod ui;
@@ -80,11 +83,14 @@ pub fn plugin(app: &mut App) {
         level::plugin,
         arrow::plugin,
         cursor::plugin,
+        gamepad::plugin,
+        touch::plugin,
         timefreeze::plugin,
         ui::plugin,
         audio::plugin,
         scorecard::plugin,
         mulligan::plugin,
+        quiver::plugin,
         pause::plugin,
         gameover::plugin,
     ))
@@ -12,7 +12,8 @@ use crate::{
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<Mulligan>()
         .add_systems(OnEnter(LevelState::NewLevel), update_mulligans)
-        .add_systems(OnEnter(Screen::Gameplay), reset_mulligans);
+        .add_systems(OnEnter(Screen::Gameplay), reset_mulligans)
+        .add_observer(take_mulligan);
 
     app.add_systems(
         Update,
@@ -28,6 +29,10 @@ pub struct Mulligan {
     mulligans_used: HashMap<usize, u8>,
 }
 
+/// Restarts the level, if it has a mulligan left
+#[derive(Event)]
+pub struct TakeMulligan;
+
 impl Mulligan {
     pub fn can_mulligan(&self, level: usize) -> bool {
         match self.mulligans_used.get(&level) {
@@ -47,16 +52,30 @@ fn update_mulligans(level: Res<Level>, mut mulligans: ResMut<Mulligan>) {
 }
 
 fn listen_for_mulligan(
+    mut commands: Commands,
     input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
     settings: Res<Settings>,
+) {
+    if input.just_pressed(settings.restart)
+        || gamepads
+            .iter()
+            .any(|pad| pad.just_pressed(settings.pad_restart))
+    {
+        commands.trigger(TakeMulligan);
+    }
+}
+
+fn take_mulligan(
+    _: Trigger<TakeMulligan>,
     mulligan: Res<Mulligan>,
     level: Res<Level>,
+    state: Option<Res<State<LevelState>>>,
     mut level_state: ResMut<NextState<LevelState>>,
 ) {
-    if !input.just_pressed(settings.restart) {
-        return;
-    }
-    if mulligan.can_mulligan(level.0) {
+    if state.is_some_and(|state| *state.get() == LevelState::Playing)
+        && mulligan.can_mulligan(level.0)
+    {
         level_state.set(LevelState::NextLevel);
     }
 }
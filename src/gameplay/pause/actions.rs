@@ -1,4 +1,7 @@
-use bevy::prelude::{Val::*, *};
+use bevy::{
+    input_focus::tab_navigation::TabGroup,
+    prelude::{Val::*, *},
+};
 
 use crate::{
     Screen,
@@ -20,6 +23,7 @@ pub fn actions() -> impl Bundle {
         },
         Pickable::IGNORE,
         Actions,
+        TabGroup::new(0),
         children![
             widgets::header("Paused"),
             widgets::button("Settings", enter_settings),
@@ -6,6 +6,7 @@ use bevy::{
 use crate::{
     gameplay::{
         GameState,
+        gamepad::{self, pad_just_pressed},
         scorecard::{ScoreCard, spawn_scorecard},
     },
     settings::SettingsState,
@@ -23,8 +24,8 @@ pub(super) fn plugin(app: &mut App) {
     .add_systems(
         Update,
         (
-            pause.run_if(in_state(GameState::Playing).and(input_just_pressed(KeyCode::Escape))),
-            unpause.run_if(in_state(GameState::Paused).and(input_just_pressed(KeyCode::Escape))),
+            pause.run_if(in_state(GameState::Playing).and(pause_pressed())),
+            unpause.run_if(in_state(GameState::Paused).and(pause_pressed())),
         ),
     )
     .add_systems(
@@ -36,6 +37,10 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+fn pause_pressed() -> impl Condition<()> {
+    input_just_pressed(KeyCode::Escape).or(pad_just_pressed(gamepad::PAUSE))
+}
+
 fn pause(mut game_state: ResMut<NextState<GameState>>) {
     game_state.set(GameState::Paused);
 }
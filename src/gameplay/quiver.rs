@@ -0,0 +1,212 @@
+//! The special arrows a level hands out.
+//!
+//! Each level fills the quiver with its own arrows when it starts. Number keys pick an arrow
+//! kind by its place in the quiver and the mouse wheel or gamepad bumpers cycle through them.
+//! Every special arrow that is fired uses one up, even the free shot of a time freeze.
+
+use std::collections::BTreeMap;
+
+use bevy::{input::mouse::AccumulatedMouseScroll, prelude::*};
+
+use crate::gameplay::{
+    GameSet, GameState,
+    arrow::ArrowKind,
+    gamepad,
+    level::{Level, LevelState, Levels, RebuildLevel},
+};
+
+/// The keys that pick the first, second, ... kind of arrow in the quiver
+const SLOT_KEYS: [KeyCode; 5] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Quiver>()
+        .add_systems(OnEnter(LevelState::Playing), fill_quiver)
+        .add_observer(
+            |_: Trigger<RebuildLevel>,
+             quiver: ResMut<Quiver>,
+             levels: Res<Levels>,
+             level: Res<Level>| {
+                fill_quiver(quiver, levels, level);
+            },
+        )
+        .add_systems(
+            Update,
+            pick_arrow
+                .in_set(GameSet::RecordInput)
+                .run_if(in_state(LevelState::Playing).and(in_state(GameState::Playing))),
+        );
+}
+
+/// The arrows left to fire in this level, and the kind the next one will be
+#[derive(Resource, Default, Debug)]
+pub struct Quiver {
+    /// special arrows left of each kind the level hands out, normal arrows never run out
+    arrows: BTreeMap<ArrowKind, u32>,
+    selected: ArrowKind,
+}
+
+impl Quiver {
+    pub fn selected(&self) -> ArrowKind {
+        self.selected
+    }
+
+    /// Whether the level hands out anything besides normal arrows
+    pub fn has_special(&self) -> bool {
+        !self.arrows.is_empty()
+    }
+
+    /// Every kind in the quiver, normal arrows first, with how many are left. Normal arrows have
+    /// `None` left, since they never run out.
+    pub fn slots(&self) -> impl Iterator<Item = (ArrowKind, Option<u32>)> + '_ {
+        std::iter::once((ArrowKind::Normal, None)).chain(
+            self.arrows
+                .iter()
+                .map(|(kind, count)| (*kind, Some(*count))),
+        )
+    }
+
+    /// Picks `kind` for the next shot, if there are any left
+    pub fn select(&mut self, kind: ArrowKind) {
+        if kind == ArrowKind::Normal || self.arrows.get(&kind).is_some_and(|count| *count > 0) {
+            self.selected = kind;
+        }
+    }
+
+    /// Picks the kind `step` places away from the selected one, skipping the ones that ran out
+    pub fn cycle(&mut self, step: isize) {
+        let available: Vec<ArrowKind> = self
+            .slots()
+            .filter(|(_, left)| left.is_none_or(|left| left > 0))
+            .map(|(kind, _)| kind)
+            .collect();
+        let Some(current) = available.iter().position(|kind| *kind == self.selected) else {
+            self.selected = ArrowKind::Normal;
+            return;
+        };
+        let next = (current as isize + step).rem_euclid(available.len() as isize);
+        self.selected = available[next as usize];
+    }
+
+    /// Uses up a `kind` arrow. Goes back to normal arrows once the last one is gone.
+    pub fn take(&mut self, kind: ArrowKind) {
+        let Some(count) = self.arrows.get_mut(&kind) else {
+            return;
+        };
+        *count = count.saturating_sub(1);
+        if *count == 0 && self.selected == kind {
+            self.selected = ArrowKind::Normal;
+        }
+    }
+}
+
+fn fill_quiver(mut quiver: ResMut<Quiver>, levels: Res<Levels>, level: Res<Level>) {
+    let arrows = levels
+        .get(level.0)
+        .map(|props| props.quiver().clone())
+        .unwrap_or_default();
+    *quiver = Quiver {
+        // normal arrows are always there
+        arrows: arrows
+            .into_iter()
+            .filter(|(kind, _)| *kind != ArrowKind::Normal)
+            .collect(),
+        selected: ArrowKind::Normal,
+    };
+}
+
+fn pick_arrow(
+    keys: Res<ButtonInput<KeyCode>>,
+    scroll: Res<AccumulatedMouseScroll>,
+    gamepads: Query<&Gamepad>,
+    mut quiver: ResMut<Quiver>,
+) {
+    let slots: Vec<ArrowKind> = quiver.slots().map(|(kind, _)| kind).collect();
+    for (key, kind) in SLOT_KEYS.iter().zip(slots) {
+        if keys.just_pressed(*key) {
+            quiver.select(kind);
+        }
+    }
+    let pressed = |button| gamepads.iter().any(|pad| pad.just_pressed(button));
+    if scroll.delta.y > 0. || pressed(gamepad::PREVIOUS_ARROW) {
+        quiver.cycle(-1);
+    } else if scroll.delta.y < 0. || pressed(gamepad::NEXT_ARROW) {
+        quiver.cycle(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quiver(arrows: impl IntoIterator<Item = (ArrowKind, u32)>) -> Quiver {
+        Quiver {
+            arrows: arrows.into_iter().collect(),
+            selected: ArrowKind::Normal,
+        }
+    }
+
+    #[test]
+    fn cycle_wraps_around() {
+        let mut quiver = quiver([(ArrowKind::Piercing, 1), (ArrowKind::Heavy, 1)]);
+        quiver.cycle(1);
+        assert_eq!(quiver.selected(), ArrowKind::Piercing);
+        quiver.cycle(1);
+        assert_eq!(quiver.selected(), ArrowKind::Heavy);
+        quiver.cycle(1);
+        assert_eq!(quiver.selected(), ArrowKind::Normal);
+        quiver.cycle(-1);
+        assert_eq!(quiver.selected(), ArrowKind::Heavy);
+    }
+
+    #[test]
+    fn cycle_skips_empty_kinds() {
+        let mut quiver = quiver([(ArrowKind::Piercing, 0), (ArrowKind::Heavy, 1)]);
+        quiver.cycle(1);
+        assert_eq!(quiver.selected(), ArrowKind::Heavy);
+        quiver.cycle(-1);
+        assert_eq!(quiver.selected(), ArrowKind::Normal);
+    }
+
+    #[test]
+    fn take_uses_up_arrows() {
+        let mut quiver = quiver([(ArrowKind::Split, 2)]);
+        quiver.select(ArrowKind::Split);
+        quiver.take(ArrowKind::Split);
+        assert_eq!(quiver.selected(), ArrowKind::Split);
+        assert!(
+            quiver
+                .slots()
+                .eq([(ArrowKind::Normal, None), (ArrowKind::Split, Some(1))])
+        );
+    }
+
+    #[test]
+    fn take_goes_back_to_normal_arrows_when_empty() {
+        let mut quiver = quiver([(ArrowKind::Split, 1)]);
+        quiver.select(ArrowKind::Split);
+        quiver.take(ArrowKind::Split);
+        assert_eq!(quiver.selected(), ArrowKind::Normal);
+        quiver.take(ArrowKind::Split);
+        assert!(
+            quiver
+                .slots()
+                .eq([(ArrowKind::Normal, None), (ArrowKind::Split, Some(0))])
+        );
+        quiver.select(ArrowKind::Split);
+        assert_eq!(quiver.selected(), ArrowKind::Normal);
+    }
+
+    #[test]
+    fn take_ignores_normal_arrows() {
+        let mut quiver = quiver([]);
+        quiver.take(ArrowKind::Normal);
+        assert_eq!(quiver.selected(), ArrowKind::Normal);
+        assert!(!quiver.has_special());
+    }
+}
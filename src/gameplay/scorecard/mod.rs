@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     Screen,
-    gameplay::level::{Level, LevelState, Levels},
+    gameplay::level::{Level, LevelProps, LevelState, Levels},
 };
 
 mod ui_widgets;
@@ -44,27 +44,40 @@ pub struct CourseScore {
     /// Otherwise, this is none.
     arrows_shot: Option<i32>,
     par: i32,
+    name: String,
 }
 
 impl CourseScore {
+    fn new(props: &LevelProps) -> Self {
+        Self {
+            arrows_shot: None,
+            par: props.par(),
+            name: props.name().to_string(),
+        }
+    }
     pub fn arrows_shot(&self) -> Option<i32> {
         self.arrows_shot
     }
     pub fn course_par(&self) -> i32 {
         self.par
     }
+    #[allow(dead_code)]
+    pub fn set_course_par(&mut self, par: i32) {
+        self.par = par;
+    }
+    /// The name of the level, or an empty string if it has none
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Takes the par and name of a level that was changed while it is being played
+    pub fn update_from(&mut self, props: &LevelProps) {
+        self.par = props.par();
+        self.name = props.name().to_string();
+    }
 }
 
 fn wipe_scorecard(mut scorecard: ResMut<ScoreCard>, levels: Res<Levels>) {
-    scorecard.wipe_with(
-        levels
-            .iter()
-            .map(|level| CourseScore {
-                arrows_shot: None,
-                par: level.par(),
-            })
-            .collect(),
-    );
+    scorecard.wipe_with(levels.iter().map(CourseScore::new).collect());
 
     //todo
 }
@@ -92,7 +105,16 @@ fn count_arrow(
     }
 }
 
-fn start_playing_level(mut scorecard: ResMut<ScoreCard>, level: Res<Level>) {
+fn start_playing_level(mut scorecard: ResMut<ScoreCard>, level: Res<Level>, levels: Res<Levels>) {
+    // generated levels are made as they are reached, so they have no course yet
+    let generated = levels
+        .iter()
+        .nth(level.0)
+        .filter(|_| scorecard.courses.len() == level.0);
+    if let Some(props) = generated {
+        scorecard.courses.push(CourseScore::new(props));
+    }
+
     let Some(course) = scorecard.get_mut(level.0) else {
         error!("Couldn't get course score for level {}", &*level);
         return;
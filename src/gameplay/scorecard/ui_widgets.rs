@@ -97,10 +97,21 @@ pub fn scorecard_row(course_no: usize, course: &CourseScore) -> impl Bundle {
     let lead = (
         Node {
             width: COURSE_W,
+            flex_direction: FlexDirection::Column,
             ..default()
         },
-        Text::new(format!("{}", course_no + 1)),
-        TextColor(Color::BLACK),
+        children![
+            (
+                Text::new(format!("{}", course_no + 1)),
+                TextColor(Color::BLACK),
+                TextFont::from_font_size(40.),
+            ),
+            (
+                Text::new(course.name()),
+                TextColor(Color::BLACK),
+                TextFont::from_font_size(18.),
+            )
+        ],
     );
 
     let score = (
@@ -125,11 +136,7 @@ pub fn scorecard_row(course_no: usize, course: &CourseScore) -> impl Bundle {
 
     (
         row_defaults(),
-        children![
-            (lead, standards.clone()),
-            (score, standards.clone()),
-            (par, standards)
-        ],
+        children![lead, (score, standards.clone()), (par, standards)],
     )
 }
 
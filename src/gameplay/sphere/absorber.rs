@@ -1,76 +1,139 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color::palettes::css::GREEN, prelude::*};
 
 use crate::{
-    gameplay::sphere::{Bouncy, Exploder, Multiplier, MustMark, Sphere, SphereAssets},
+    gameplay::{
+        arrow::{Arrow, NockedOn},
+        level::SphereType,
+        sphere::{
+            Bouncy, DestroySphere, HitByExplosion, RegisterSphereKind, SPHERE_RADIUS, Sphere,
+            SphereAssets, SphereKind, SphereKinds,
+        },
+    },
     third_party::avian3d::GameLayer,
 };
 
-/// WIP, need to fix a few systems
-#[derive(Component)]
-#[require(Sphere)]
-#[require(MustMark)]
+/// How many properties an absorber can hold
+pub const MAX_ABSORBED: usize = 3;
+
+/// The sphere types an absorber can take the behaviour of
+const ABSORBABLE: [SphereType; 5] = [
+    SphereType::BOUNCY,
+    SphereType::MULTIPLIER,
+    SphereType::EXPLODER,
+    SphereType::TIME_FREEZE,
+    SphereType::GRAVITY,
+];
+
+/// Soaks up the behaviour of the spheres that touch it, up to [`MAX_ABSORBED`] of them.
+///
+/// Each absorbed property shows as a small sphere of its kind next to the absorber. Arrows,
+/// bouncy balls, explosions and lightning don't break an absorber until it is full, then the
+/// next hit does. It has to be hit to clear the level like any [`MustMark`](super::MustMark)
+/// sphere, and stops counting once it breaks.
+#[derive(Component, Default)]
+#[require(Sphere, Absorbed)]
 pub struct Absorber;
 
+impl SphereType {
+    pub const ABSORBER: Self = Self::new("Absorber");
+}
+
+/// The sphere types an absorber has taken the behaviour of, in the order it took them
+#[derive(Component, Default)]
+pub struct Absorbed(Vec<SphereType>);
+
+impl Absorbed {
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= MAX_ABSORBED
+    }
+}
+
 pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        base_color: GREEN.into(),
+        reflectance: 1.,
+        specular_transmission: 0.90,
+        diffuse_transmission: 0.5,
+        thickness: 0.6,
+        ior: 1.5,
+        perceptual_roughness: 0.4,
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Absorber>(SphereType::ABSORBER)
+            .material(material)
+            .collides_with([GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls])
+            .must_mark()
+            .with(Restitution::PERFECTLY_ELASTIC),
+    );
     app.add_observer(insert_absorber);
 }
-fn insert_absorber(
-    trigger: Trigger<OnAdd, Absorber>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
+fn insert_absorber(trigger: Trigger<OnAdd, Absorber>, mut commands: Commands) {
     commands
         .entity(trigger.target())
-        .insert((
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls],
-            ),
-            MeshMaterial3d(assets.absorber.clone()),
-            Restitution::PERFECTLY_ELASTIC,
-        ))
-        .observe(absorb_property::<Bouncy>)
-        .observe(absorb_property::<Multiplier>)
-        .observe(absorb_property::<Exploder>);
+        .observe(absorb_property)
+        .observe(hit_on_collision)
+        .observe(|trigger: Trigger<HitByExplosion>, mut commands: Commands| {
+            commands.trigger_targets(DestroySphere, trigger.target());
+        });
 }
 
-fn absorb_property<Prop>(
+// `destroy_sphere` ignores hits until the absorber is full
+fn hit_on_collision(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
-    spheres: Query<&MeshMaterial3d<StandardMaterial>, (With<Sphere>, With<Prop>)>,
-    absorbers_without_prop: Query<
-        &MeshMaterial3d<StandardMaterial>,
-        (Without<Prop>, With<Absorber>),
-    >,
+    hitters: Query<(), (Or<(With<Arrow>, With<Bouncy>)>, Without<NockedOn>)>,
     colliders: Query<&ColliderOf>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) where
-    Prop: Component + Default,
-{
-    info!("inserting property on absorber");
-    let Ok(absorber_material) = absorbers_without_prop.get(trigger.target()) else {
+) {
+    let Ok(collider) = colliders.get(trigger.collider) else {
         return;
     };
-    let Ok(collider) = colliders.get(trigger.event().collider) else {
+    if hitters.get(collider.body).is_err() {
+        return;
+    }
+    commands.trigger_targets(DestroySphere, trigger.target());
+}
+
+fn absorb_property(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    mut absorbers: Query<&mut Absorbed>,
+    sphere_types: Query<&SphereType>,
+    colliders: Query<&ColliderOf>,
+    kinds: Res<SphereKinds>,
+    assets: Res<SphereAssets>,
+) {
+    let Ok(mut absorbed) = absorbers.get_mut(trigger.target()) else {
         return;
     };
-    let Ok(collider_material) = spheres.get(collider.body) else {
+    let Ok(collider) = colliders.get(trigger.collider) else {
         return;
     };
-
-    let collider_color = {
-        let Some(material) = materials.get(collider_material) else {
-            return;
-        };
-        material.base_color
+    let Ok(sphere_type) = sphere_types.get(collider.body) else {
+        return;
     };
+    if absorbed.is_full() || absorbed.0.contains(sphere_type) || !ABSORBABLE.contains(sphere_type) {
+        return;
+    }
+    info!("absorbing {sphere_type} property");
 
-    let Some(absorber_material) = materials.get_mut(absorber_material) else {
+    let Some(material) = kinds.material(sphere_type) else {
         return;
     };
+    kinds.insert_behaviour(sphere_type, &mut commands.entity(trigger.target()));
 
-    absorber_material.base_color.mix_assign(collider_color, 0.3);
-
-    commands.entity(trigger.target()).insert(Prop::default());
+    // spread around the absorber, one for each property it can hold
+    let angle = absorbed.0.len() as f32 * TAU / MAX_ABSORBED as f32 + FRAC_PI_2;
+    let offset = Vec2::from_angle(angle) * (SPHERE_RADIUS + 0.3);
+    commands.spawn((
+        Name::new(format!("Absorbed {sphere_type}")),
+        Mesh3d(assets.mesh.clone()),
+        MeshMaterial3d(material),
+        Transform::from_translation(offset.extend(0.)).with_scale(Vec3::splat(0.3)),
+        ChildOf(trigger.target()),
+    ));
+    absorbed.0.push(sphere_type.clone());
 }
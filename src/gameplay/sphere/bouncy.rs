@@ -1,11 +1,13 @@
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color::palettes::css::YELLOW, prelude::*};
 
 use crate::{
     gameplay::{
-        arrow::{Arrow, NockedOn},
-        level::Walls,
-        sphere::{Absorber, FromAbsorberMultiply, MustMark, ShouldMultiply, Sphere, SphereAssets},
+        arrow::{Arrow, ArrowKind, NockedOn},
+        level::{MovingWall, SphereType, Walls},
+        sphere::{
+            Absorber, FromAbsorberMultiply, RegisterSphereKind, ShouldMultiply, Sphere, SphereKind,
+        },
     },
     third_party::avian3d::GameLayer,
     world::GAME_PLANE,
@@ -13,31 +15,28 @@ use crate::{
 
 #[derive(Component, Default)]
 #[require(Sphere)]
-#[require(MustMark)]
 pub struct Bouncy;
 
+impl SphereType {
+    pub const BOUNCY: Self = Self::new("Bouncy");
+}
+
 pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        base_color: YELLOW.into(),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Bouncy>(SphereType::BOUNCY)
+            .material(material)
+            .collides_with([GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls])
+            .must_mark(),
+    );
     app.add_observer(insert_bouncy);
 }
-fn insert_bouncy(
-    trigger: Trigger<OnAdd, Bouncy>,
-    absorbers: Query<(), With<Absorber>>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
-    let mut commands = commands.entity(trigger.target());
-
-    if absorbers.get(trigger.target()).is_err() {
-        commands.insert((
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls],
-            ),
-            MeshMaterial3d(assets.bouncy.clone()),
-        ));
-    }
-
+fn insert_bouncy(trigger: Trigger<OnAdd, Bouncy>, mut commands: Commands) {
     commands
+        .entity(trigger.target())
         .insert((Restitution::PERFECTLY_ELASTIC, Friction::ZERO))
         .observe(on_multiply)
         .observe(despawn_arrow_on_contact)
@@ -49,7 +48,7 @@ fn despawn_arrow_on_contact(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
     absorbers: Query<(), With<Absorber>>,
-    arrows: Query<(), (With<Arrow>, Without<NockedOn>)>,
+    arrows: Query<&ArrowKind, (With<Arrow>, Without<NockedOn>)>,
     colliders: Query<&ColliderOf>,
 ) {
     if absorbers.get(trigger.target()).is_ok() {
@@ -60,7 +59,11 @@ fn despawn_arrow_on_contact(
     let Ok(collider) = colliders.get(event.collider) else {
         return;
     };
-    if arrows.get(collider.body).is_err() {
+    let Ok(kind) = arrows.get(collider.body) else {
+        return;
+    };
+    // piercing arrows fly on through
+    if *kind == ArrowKind::Piercing {
         return;
     }
     commands.entity(collider.body).try_despawn();
@@ -68,7 +71,7 @@ fn despawn_arrow_on_contact(
 
 fn increase_velocity_on_collision(
     trigger: Trigger<OnCollisionStart>,
-    valid_colliders: Query<(), (Without<NockedOn>, Without<Walls>)>,
+    valid_colliders: Query<(), (Without<NockedOn>, Without<Walls>, Without<MovingWall>)>,
     colliders: Query<&ColliderOf>,
     mut velocity: Query<&mut LinearVelocity>,
 ) {
@@ -118,7 +121,7 @@ fn on_multiply(
 
         commands.spawn((
             Name::new("Bouncy Replica"),
-            Bouncy,
+            SphereType::BOUNCY,
             FromAbsorberMultiply::default(),
             transform,
             LinearVelocity(velocity),
@@ -4,8 +4,8 @@ use crate::{
     gameplay::{
         GameSet,
         arrow::NockedOn,
-        level::Walls,
-        sphere::{Absorber, SphereAssets},
+        level::{MovingWall, Walls},
+        sphere::{Absorbed, SphereAssets},
     },
     loading::LoadingState,
     third_party::avian3d::GameLayer,
@@ -28,7 +28,8 @@ pub(super) fn plugin(app: &mut App) {
             Update,
             (
                 tick_being_destroyed.in_set(GameSet::TickTimers),
-                (despawn_destroyed, limit_gib_population).in_set(GameSet::Update),
+                (despawn_destroyed, limit_gib_population, shade_by_hit_points)
+                    .in_set(GameSet::Update),
             ),
         )
         .add_systems(PostUpdate, marked_for_removal_cleanup)
@@ -50,6 +51,10 @@ fn marked_for_removal_cleanup(
 #[derive(Event)]
 pub struct DestroySphere;
 
+/// Triggered on a sphere when it shatters, after its last hit point is gone
+#[derive(Event)]
+pub struct SphereBroken;
+
 #[derive(Component)]
 #[relationship(relationship_target = GibChild)]
 pub struct GibsOf(Entity);
@@ -58,6 +63,64 @@ pub struct GibsOf(Entity);
 #[relationship_target(relationship = GibsOf)]
 pub struct GibChild(Entity);
 
+/// How many more hits a sphere can take, the last one breaks it.
+///
+/// Spheres without it break on their first hit. Each hit lightens the sphere, so armored
+/// spheres start out darker than the rest of their kind.
+#[derive(Component)]
+pub struct HitPoints(pub u32);
+
+/// How much darker each hit point past the first makes a sphere
+const HIT_POINT_SHADE: f32 = 0.15;
+
+/// The material of the sphere's kind, before it was shaded by its hit points
+#[derive(Component)]
+struct UnshadedMaterial(Handle<StandardMaterial>);
+
+/// Takes a hit point off of `sphere`. Returns true if that was its last one.
+fn take_hit(sphere: Entity, hit_points: &mut Query<&mut HitPoints>) -> bool {
+    let Ok(mut points) = hit_points.get_mut(sphere) else {
+        return true;
+    };
+    points.0 = points.0.saturating_sub(1);
+    points.0 == 0
+}
+
+fn shade_by_hit_points(
+    mut commands: Commands,
+    spheres: Query<
+        (
+            Entity,
+            &HitPoints,
+            &MeshMaterial3d<StandardMaterial>,
+            Option<&UnshadedMaterial>,
+        ),
+        Changed<HitPoints>,
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (sphere, points, material, unshaded) in &spheres {
+        let unshaded = match unshaded {
+            Some(unshaded) => unshaded.0.clone(),
+            None => {
+                commands
+                    .entity(sphere)
+                    .insert(UnshadedMaterial(material.0.clone()));
+                material.0.clone()
+            }
+        };
+        let Some(mut shaded) = materials.get(&unshaded).cloned() else {
+            continue;
+        };
+        let extra_hits = points.0.saturating_sub(1) as f32;
+        shaded.base_color = shaded.base_color.darker(HIT_POINT_SHADE * extra_hits);
+        shaded.emissive = shaded.emissive * (1. - HIT_POINT_SHADE).powf(extra_hits);
+        commands
+            .entity(sphere)
+            .insert(MeshMaterial3d(materials.add(shaded)));
+    }
+}
+
 fn add_destroyable_sphere(trigger: Trigger<OnAdd, Sphere>, mut commands: Commands) {
     commands.entity(trigger.target()).observe(destroy_sphere);
 }
@@ -70,53 +133,44 @@ struct Gib;
 // listener should ONLY be on the Sphere component.
 fn destroy_sphere(
     trigger: Trigger<DestroySphere>,
-    absorber: Query<(), With<Absorber>>,
+    absorbers: Query<&Absorbed>,
     marked: Query<(), With<MarkForImmediateRemoval>>,
+    mut hit_points: Query<&mut HitPoints>,
     mut commands: Commands,
     meshes: Res<GibMeshes>,
     transforms: Query<(&Transform, &MeshMaterial3d<StandardMaterial>)>,
 ) {
-    // absorbers are the exception and will be custom despawned.
-    // you would ideally attach this listener to all balls but ehh why
-    if absorber.get(trigger.target()).is_ok() {
+    // absorbers shrug off hits until they are full
+    if absorbers
+        .get(trigger.target())
+        .is_ok_and(|absorbed| !absorbed.is_full())
+    {
         return;
     }
 
     if marked.get(trigger.target()).is_ok() {
         return;
     }
+    if !take_hit(trigger.target(), &mut hit_points) {
+        return;
+    }
 
     let Ok((sphere_transform, sphere_material)) = transforms.get(trigger.target()) else {
         return;
     };
 
-    let mut meshes_to_spawn = Vec::with_capacity(meshes.meshes.len());
-
-    for (transform, mesh_handle, collider) in meshes.meshes.iter() {
-        let new_transform =
-            Transform::from_translation(sphere_transform.translation + transform.translation)
-                .with_rotation(transform.rotation);
-
-        meshes_to_spawn.push((
-            Name::new("Gib Piece"),
-            Gib,
-            new_transform,
-            Mesh3d(mesh_handle.clone()),
-            MeshMaterial3d(sphere_material.0.clone()),
-            collider.clone(),
-            RigidBody::Dynamic,
-            Visibility::Visible,
-            CollisionLayers::new(GameLayer::Gibs, [GameLayer::Gibs, GameLayer::Backdrop]),
-            BeingDestroyed(Timer::new(Duration::from_secs(3), TimerMode::Once)),
-        ))
-    }
+    commands.trigger_targets(SphereBroken, trigger.target());
+    meshes.spawn_gibs(
+        &mut commands,
+        sphere_transform.translation,
+        &sphere_material.0,
+    );
 
     commands.entity(trigger.target()).insert((
         Visibility::Hidden,
         Disabled,
         MarkForImmediateRemoval,
     ));
-    commands.spawn_batch(meshes_to_spawn);
 }
 fn tick_being_destroyed(mut being_destroyed: Query<&mut BeingDestroyed>, time: Res<Time>) {
     for mut timer in &mut being_destroyed {
@@ -165,6 +219,36 @@ impl GibMeshes {
     pub fn is_ready(&self) -> bool {
         self.is_ready
     }
+
+    /// Shatters a sphere's worth of gibs out from `at`
+    pub fn spawn_gibs(
+        &self,
+        commands: &mut Commands,
+        at: Vec3,
+        material: &Handle<StandardMaterial>,
+    ) {
+        let mut meshes_to_spawn = Vec::with_capacity(self.meshes.len());
+
+        for (transform, mesh_handle, collider) in self.meshes.iter() {
+            let new_transform = Transform::from_translation(at + transform.translation)
+                .with_rotation(transform.rotation);
+
+            meshes_to_spawn.push((
+                Name::new("Gib Piece"),
+                Gib,
+                new_transform,
+                Mesh3d(mesh_handle.clone()),
+                MeshMaterial3d(material.clone()),
+                collider.clone(),
+                RigidBody::Dynamic,
+                Visibility::Visible,
+                CollisionLayers::new(GameLayer::Gibs, [GameLayer::Gibs, GameLayer::Backdrop]),
+                BeingDestroyed(Timer::new(Duration::from_secs(3), TimerMode::Once)),
+            ))
+        }
+
+        commands.spawn_batch(meshes_to_spawn);
+    }
 }
 // this function makes sure an extreme number of gibs don't exist in the world, causing lag
 fn limit_gib_population(new_gibs: Query<Entity, With<Gib>>, mut commands: Commands) {
@@ -222,9 +306,10 @@ fn handle_mark_balls(trigger: Trigger<OnAdd, MustMark>, mut commands: Commands)
 fn mark_for_deletion(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
-    valid_colliders: Query<(), (Without<NockedOn>, Without<Walls>)>,
+    valid_colliders: Query<(), (Without<NockedOn>, Without<Walls>, Without<MovingWall>)>,
     colliders: Query<&ColliderOf>,
     marks: Query<&MarkedForDeletion>,
+    mut hit_points: Query<&mut HitPoints>,
     //mut meshes: ResMut,
 ) {
     let Ok(ball_collider) = colliders.get(trigger.target()) else {
@@ -241,6 +326,9 @@ fn mark_for_deletion(
     if valid_colliders.get(collider.body).is_err() {
         return;
     }
+    if !take_hit(ball_collider.body, &mut hit_points) {
+        return;
+    }
     commands.entity(ball_collider.body).insert((
         MarkedForDeletion,
         OutlineVolume {
@@ -2,24 +2,87 @@ use std::{f32::consts::FRAC_PI_2, time::Duration};
 
 use avian3d::prelude::*;
 use bevy::{
-    color::palettes::css::{ORANGE, RED, YELLOW},
+    color::palettes::css::{ORANGE, RED, WHITE, YELLOW},
     prelude::*,
 };
 use bevy_trauma_shake::Shake;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     gameplay::{
         GameSet, GameState,
-        arrow::NockedOn,
-        sphere::{Absorber, DestroySphere, FromAbsorberMultiply, Sphere, SphereAssets},
+        arrow::{Arrow, NockedOn},
+        level::{LevelState, SphereType},
+        sphere::{
+            Bouncy, DestroySphere, FromAbsorberMultiply, RegisterSphereKind, Sphere, SphereKind,
+        },
     },
     third_party::avian3d::GameLayer,
 };
 
-const EXPLOSION_RADIUS: f32 = 8.;
+/// How far an exploder's blast reaches, unless its level says otherwise
+pub const EXPLOSION_RADIUS: f32 = 8.;
+
+/// How an exploder goes off.
+///
+/// Every exploder comes with the default one, and levels can tune each of them. Anything missing
+/// from a level file is filled in like [`Blast::default`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Blast {
+    /// how far the explosion reaches from the sphere's center
+    pub radius: f32,
+    /// fuse ticks when lit by an arrow or a ball
+    pub fuse: usize,
+    /// fuse ticks when lit by another explosion or by lightning
+    pub chained_fuse: usize,
+    /// seconds between fuse ticks
+    pub tick: f32,
+    /// how hard arrows and balls caught in the blast get thrown away from it
+    pub strength: f32,
+}
+
+impl Default for Blast {
+    fn default() -> Self {
+        Self {
+            radius: EXPLOSION_RADIUS,
+            fuse: 3,
+            chained_fuse: 1,
+            tick: 0.2,
+            strength: 0.,
+        }
+    }
+}
+
+/// Draws which exploder set off which, toggled with the debug UI
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ChainReactionGizmos;
+
+impl SphereType {
+    pub const EXPLODER: Self = Self::new("Exploder");
+}
 
 pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        //base_color: RED.into(),
+        emissive: Srgba::rgb(5., 0., 0.).into(),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Exploder>(SphereType::EXPLODER)
+            .material(material)
+            .collides_with([GameLayer::ArrowSensor, GameLayer::Sphere])
+            .sensor()
+            .with(Blast::default()),
+    );
     app.init_resource::<ExploderAssets>();
+    app.insert_gizmo_config(
+        ChainReactionGizmos,
+        GizmoConfig {
+            enabled: false,
+            ..default()
+        },
+    );
     app.add_observer(insert_exploder)
         .add_systems(
             Update,
@@ -29,7 +92,7 @@ pub(super) fn plugin(app: &mut App) {
         )
         .add_systems(
             Update,
-            (animate_indicator, explode)
+            (animate_indicator, explode, draw_chain_reactions)
                 .in_set(GameSet::Update)
                 .run_if(in_state(GameState::Playing)),
         );
@@ -43,43 +106,27 @@ impl FromWorld for ExploderAssets {
     fn from_world(world: &mut World) -> Self {
         let mut meshes = world.resource_mut::<Assets<Mesh>>();
 
-        let torus = meshes.add(Extrusion::new(
-            Annulus::new(EXPLOSION_RADIUS - 0.5, EXPLOSION_RADIUS),
-            0.2,
-        ));
+        let torus = meshes.add(torus(EXPLOSION_RADIUS));
 
         Self { torus }
     }
 }
 
+/// A ring on the edge of a blast `radius` across
+fn torus(radius: f32) -> Extrusion<Annulus> {
+    Extrusion::new(Annulus::new((radius - 0.5).max(0.), radius), 0.2)
+}
+
 #[derive(Component, Default)]
 #[require(Sphere)]
 pub struct Exploder;
 
-fn insert_exploder(
-    trigger: Trigger<OnAdd, Exploder>,
-    absorbers: Query<(), With<Absorber>>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
-    let mut commands = commands.entity(trigger.target());
-    if absorbers.get(trigger.target()).is_err() {
-        commands.insert((
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::ArrowSensor, GameLayer::Sphere],
-            ),
-            MeshMaterial3d(assets.exploder.clone()),
-        ));
-    }
-
+fn insert_exploder(trigger: Trigger<OnAdd, Exploder>, mut commands: Commands) {
     commands
+        .entity(trigger.target())
         .observe(light_fuse_on_collision)
         .observe(light_fuse)
-        .observe(|trigger: Trigger<HitByExplosion>, mut commands: Commands| {
-            //light a much smaller fuse if hit by an explosion
-            commands.trigger_targets(LightFuse(1), trigger.target());
-        });
+        .observe(light_fuse_on_explosion);
 }
 
 #[derive(Component, Debug)]
@@ -89,23 +136,48 @@ struct Fuse {
 }
 
 impl Fuse {
-    fn new(ticks: usize) -> Self {
+    fn new(ticks: usize, tick: f32) -> Self {
+        // ticks too long to count, like `inf`, never go off
+        let tick = Duration::try_from_secs_f32(tick.max(0.)).unwrap_or(Duration::MAX);
         Self {
-            timer: Timer::new(Duration::from_millis(200), TimerMode::Repeating),
+            timer: Timer::new(tick, TimerMode::Repeating),
             countdown: ticks,
         }
     }
 }
 
-#[derive(Event)]
-pub struct LightFuse(pub usize);
+/// A blast set off without an exploder, gone once it has gone off
+#[derive(Component)]
+struct Detonation;
+
+/// Sets off `blast` at `at` right away, like the tip of an explosive arrow does
+pub fn detonate(commands: &mut Commands, at: Vec3, blast: Blast) {
+    commands.spawn((
+        Name::new("Detonation"),
+        Detonation,
+        StateScoped(LevelState::Playing),
+        Transform::from_translation(at),
+        blast,
+        Fuse::new(0, blast.tick),
+    ));
+}
+
+/// Lights an exploder's fuse, if it isn't lit already
+#[derive(Event, Clone, Copy)]
+pub enum LightFuse {
+    /// hit by an arrow or a ball, lights its [`Blast::fuse`]
+    Hit,
+    /// caught in another explosion or struck by lightning, lights its much smaller
+    /// [`Blast::chained_fuse`]
+    Chained,
+}
 
 #[derive(Component)]
 struct Indicator(Entity);
 
-fn indicator(assets: &ExploderAssets, materials: &mut Assets<StandardMaterial>) -> impl Bundle {
+fn indicator(mesh: Handle<Mesh>, materials: &mut Assets<StandardMaterial>) -> impl Bundle {
     (
-        Mesh3d(assets.torus.clone()),
+        Mesh3d(mesh),
         MeshMaterial3d(materials.add(Color::from(YELLOW))),
         Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2)),
     )
@@ -124,17 +196,63 @@ fn light_fuse_on_collision(
     if ignore.get(collider.body).is_ok() {
         return;
     }
-    commands.trigger_targets(LightFuse(3), trigger.target());
+    commands.trigger_targets(LightFuse::Hit, trigger.target());
+}
+
+/// One exploder setting off another, kept until the level is left so the whole chain reaction
+/// can be looked at afterwards
+#[derive(Component)]
+struct ChainLink {
+    to: Entity,
+    from_location: Vec3,
+    to_location: Vec3,
+    /// how many exploders went off before the one at `from_location` in the same chain reaction
+    depth: usize,
+}
+
+fn light_fuse_on_explosion(
+    trigger: Trigger<HitByExplosion>,
+    mut commands: Commands,
+    exploders: Query<(&Transform, Has<Fuse>)>,
+    links: Query<&ChainLink>,
+) {
+    let exploder = trigger.target();
+    let Ok((transform, lit)) = exploders.get(exploder) else {
+        return;
+    };
+    // something else already set it off
+    if lit {
+        return;
+    }
+    let event = trigger.event();
+    let depth = links
+        .iter()
+        .find(|link| link.to == event.exploder)
+        .map_or(0, |link| link.depth + 1);
+    commands.spawn((
+        Name::new("Chain Reaction Link"),
+        StateScoped(LevelState::Playing),
+        ChainLink {
+            to: exploder,
+            from_location: event.location().extend(transform.translation.z),
+            to_location: transform.translation,
+            depth,
+        },
+    ));
+
+    commands.trigger_targets(LightFuse::Chained, exploder);
 }
 
 fn light_fuse(
     trigger: Trigger<LightFuse>,
     mut commands: Commands,
-    mut exploders: Query<(Entity, Has<Fuse>, Has<Indicator>), With<Exploder>>,
+    mut exploders: Query<(Entity, &Blast, Has<Fuse>, Has<Indicator>), With<Exploder>>,
     assets: Res<ExploderAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    let Ok((exploder, current_fuse, has_indicator)) = exploders.get_mut(trigger.target()) else {
+    let Ok((exploder, blast, current_fuse, has_indicator)) = exploders.get_mut(trigger.target())
+    else {
         return;
     };
 
@@ -143,7 +261,12 @@ fn light_fuse(
     }
 
     if !has_indicator {
-        let indicator = commands.spawn(indicator(&assets, &mut materials)).id();
+        let mesh = if blast.radius == EXPLOSION_RADIUS {
+            assets.torus.clone()
+        } else {
+            meshes.add(torus(blast.radius))
+        };
+        let indicator = commands.spawn(indicator(mesh, &mut materials)).id();
 
         commands
             .entity(exploder)
@@ -151,9 +274,13 @@ fn light_fuse(
             .add_child(indicator);
     }
 
+    let ticks = match trigger.event() {
+        LightFuse::Hit => blast.fuse,
+        LightFuse::Chained => blast.chained_fuse,
+    };
     commands
         .entity(exploder)
-        .insert((Fuse::new(trigger.event().0)));
+        .insert(Fuse::new(ticks, blast.tick));
 }
 
 fn tick_explosion(mut fuses: Query<&mut Fuse>, time: Res<Time>) {
@@ -179,7 +306,7 @@ fn animate_indicator(
         };
         let color: Color = match fuse {
             Some(fuse) => match fuse.countdown {
-                3 => YELLOW.into(),
+                3.. => YELLOW.into(),
                 2 => ORANGE.into(),
                 _ => RED.into(),
             },
@@ -194,12 +321,14 @@ fn animate_indicator(
 
 #[derive(Event)]
 pub struct HitByExplosion {
+    exploder: Entity,
     explosion_location: Vec2,
     exploder_was_from_multiply: bool,
 }
 impl HitByExplosion {
-    fn new(explosion_location: Vec2, was_from_multiple: bool) -> Self {
+    fn new(exploder: Entity, explosion_location: Vec2, was_from_multiple: bool) -> Self {
         Self {
+            exploder,
             explosion_location,
             exploder_was_from_multiply: was_from_multiple,
         }
@@ -214,18 +343,26 @@ impl HitByExplosion {
 
 fn explode(
     mut commands: Commands,
-    fuses: Query<(Entity, &Transform, Has<FromAbsorberMultiply>, &Fuse)>,
+    fuses: Query<(
+        Entity,
+        &Transform,
+        &Blast,
+        Has<FromAbsorberMultiply>,
+        Has<Detonation>,
+        &Fuse,
+    )>,
     mut shake: Single<&mut Shake>,
     colliders: Query<&ColliderOf>,
+    mut thrown: Query<(&Position, &mut ExternalImpulse), Or<(With<Arrow>, With<Bouncy>)>>,
     spatial_query: SpatialQuery,
 ) {
     let mut should_shake = false;
-    for (entity, transform, from_multiply, fuse) in fuses {
+    for (entity, transform, blast, from_multiply, detonation, fuse) in fuses {
         if fuse.countdown != 0 {
             continue;
         }
 
-        let shape = Collider::sphere(EXPLOSION_RADIUS);
+        let shape = Collider::sphere(blast.radius);
         let origin = transform.translation;
         let rotation = Quat::default();
         let filter = SpatialQueryFilter::from_mask([GameLayer::Sphere, GameLayer::Arrow]);
@@ -243,15 +380,38 @@ fn explode(
                 }
                 continue;
             }
+            if let Ok((position, mut impulse)) = thrown.get_mut(body) {
+                // weaker towards the edge of the blast
+                let offset = position.0 - origin;
+                let scale = (1. - offset.length() / blast.radius).max(0.);
+                impulse.apply_impulse(offset.normalize_or_zero() * blast.strength * scale);
+            }
             commands.trigger_targets(
-                HitByExplosion::new(transform.translation.xy(), from_multiply),
+                HitByExplosion::new(entity, transform.translation.xy(), from_multiply),
                 body,
             );
         }
         should_shake = true;
-        commands.entity(entity).try_remove::<Fuse>();
+        if detonation {
+            commands.entity(entity).try_despawn();
+        } else {
+            commands.entity(entity).try_remove::<Fuse>();
+        }
     }
     if should_shake {
         shake.add_trauma(0.3);
     }
 }
+
+fn draw_chain_reactions(links: Query<&ChainLink>, mut gizmos: Gizmos<ChainReactionGizmos>) {
+    for link in &links {
+        // later links in the chain get redder
+        let color = Color::from(YELLOW).mix(&RED.into(), (link.depth as f32 / 4.).min(1.));
+        gizmos.arrow(link.from_location, link.to_location, color);
+        gizmos.sphere(Isometry3d::from_translation(link.to_location), 1., color);
+        if link.depth == 0 {
+            // where the chain reaction started
+            gizmos.sphere(Isometry3d::from_translation(link.from_location), 1., WHITE);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use avian3d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{PINK_400, SKY_600},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gameplay::{
+        level::{LevelState, SphereType},
+        sphere::{RegisterSphereKind, Sphere, SphereKind},
+    },
+    third_party::avian3d::GameLayer,
+};
+
+/// Pushes arrows and spheres around the sphere it's on.
+///
+/// Every sphere kind with a field comes with its own, and levels can give any sphere a different
+/// one. Anything missing from a level file is filled in like [`ForceField::default`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForceField {
+    pub mode: FieldMode,
+    /// how far out the field reaches from the sphere's center
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    /// how hard the field pushes at the sphere's center
+    #[serde(default = "default_strength")]
+    pub strength: f32,
+    /// how the push weakens towards `radius`
+    #[serde(default)]
+    pub falloff: Falloff,
+}
+
+fn default_radius() -> f32 {
+    10.
+}
+fn default_strength() -> f32 {
+    100.
+}
+
+/// The pull of the gravity sphere
+impl Default for ForceField {
+    fn default() -> Self {
+        Self::new(FieldMode::Attract)
+    }
+}
+
+impl ForceField {
+    pub fn new(mode: FieldMode) -> Self {
+        Self {
+            mode,
+            radius: default_radius(),
+            strength: default_strength(),
+            falloff: Falloff::default(),
+        }
+    }
+
+    /// The force on something `offset` away from the sphere's center
+    fn force(&self, offset: Vec3) -> Vec3 {
+        let distance = offset.length();
+        if distance >= self.radius {
+            return Vec3::ZERO;
+        }
+        let direction = match self.mode {
+            FieldMode::Attract => -offset,
+            FieldMode::Repel => offset,
+            FieldMode::Vortex => Vec3::Z.cross(offset),
+        };
+        direction.normalize_or_zero() * self.strength * self.falloff.scale(distance / self.radius)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FieldMode {
+    /// Pulls towards the sphere
+    Attract,
+    /// Pushes away from the sphere
+    Repel,
+    /// Pushes around the sphere counter clockwise. Negative strengths push clockwise.
+    Vortex,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Falloff {
+    /// As strong at the edge as in the middle
+    Constant,
+    /// Weakens evenly to nothing at the edge
+    Linear,
+    /// Stays strong for most of the way, then drops off to nothing at the edge
+    #[default]
+    Quadratic,
+}
+
+impl Falloff {
+    /// How much of the field's strength is left `fraction` of the way to its edge
+    fn scale(&self, fraction: f32) -> f32 {
+        match self {
+            Self::Constant => 1.,
+            Self::Linear => 1. - fraction,
+            Self::Quadratic => 1. - fraction * fraction,
+        }
+    }
+}
+
+/// Pushes things away with its [`ForceField`]
+#[derive(Component, Default)]
+#[require(Sphere)]
+pub struct Repulsor;
+
+/// Spins things around it with its [`ForceField`]
+#[derive(Component, Default)]
+#[require(Sphere)]
+pub struct Vortex;
+
+impl SphereType {
+    pub const REPULSOR: Self = Self::new("Repulsor");
+    pub const VORTEX: Self = Self::new("Vortex");
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let repulsor = StandardMaterial {
+        base_color: PINK_400.into(),
+        emissive: LinearRgba::new(1., 0.2, 0.6, 1.),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Repulsor>(SphereType::REPULSOR)
+            .material(repulsor)
+            .collides_with([GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls])
+            .must_mark()
+            .with((
+                Restitution::PERFECTLY_ELASTIC,
+                Dominance(1),
+                ForceField::new(FieldMode::Repel),
+            )),
+    );
+
+    let vortex = StandardMaterial {
+        base_color: SKY_600.into(),
+        perceptual_roughness: 0.2,
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Vortex>(SphereType::VORTEX)
+            .material(vortex)
+            .collides_with([GameLayer::Arrow, GameLayer::Sphere, GameLayer::Walls])
+            .must_mark()
+            .with((
+                Restitution::PERFECTLY_ELASTIC,
+                Dominance(1),
+                ForceField::new(FieldMode::Vortex),
+            )),
+    );
+
+    app.add_systems(
+        FixedUpdate,
+        apply_force_fields.run_if(in_state(LevelState::Playing)),
+    );
+}
+
+fn apply_force_fields(
+    fields: Query<(Entity, &ForceField, &Position)>,
+    colliders: Query<&ColliderOf>,
+    positions: Query<&Position>,
+    mut forces: Query<&mut ExternalForce>,
+    spatial_query: SpatialQuery,
+) {
+    for (sphere, field, position) in fields {
+        let shape = Collider::sphere(field.radius);
+        let origin = position.0;
+        let rotation = Quat::default();
+        let filter = SpatialQueryFilter::from_mask([GameLayer::Sphere, GameLayer::Arrow]);
+        let hits = spatial_query.shape_intersections(&shape, origin, rotation, &filter);
+
+        for hit in hits {
+            let Ok(collider) = colliders.get(hit) else {
+                continue;
+            };
+            let body = collider.body;
+            if body == sphere {
+                continue;
+            }
+            let Ok(other_position) = positions.get(body) else {
+                continue;
+            };
+            let Ok(mut force) = forces.get_mut(body) else {
+                warn!("couldn't get external force for other body");
+                continue;
+            };
+            force.persistent = false;
+            force.apply_force(field.force(other_position.0 - origin));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! The registry of sphere kinds.
+//!
+//! Each kind of sphere registers a [`SphereKind`] from its own module. Spheres spawned with a
+//! [`SphereType`] get their material, collision layers and behaviour from here, so level files
+//! and the editor only ever deal in names.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    gameplay::{level::SphereType, sphere::MustMark},
+    third_party::avian3d::GameLayer,
+};
+
+type InsertBundle = Box<dyn Fn(&mut EntityCommands) + Send + Sync>;
+
+/// Everything that makes a kind of sphere what it is
+pub struct SphereKind {
+    sphere_type: SphereType,
+    material: StandardMaterial,
+    /// the layers the sphere collides with, it is always on [`GameLayer::Sphere`] itself
+    collides_with: LayerMask,
+    /// has to be hit to clear the level, instead of breaking on contact
+    must_mark: bool,
+    /// lets arrows through instead of bouncing them off
+    sensor: bool,
+    /// inserts the marker component, whose observers give the kind its behaviour, and anything
+    /// else the kind needs
+    insert: Vec<InsertBundle>,
+}
+
+impl SphereKind {
+    /// A kind of sphere that behaves like its `Marker` component.
+    ///
+    /// It collides with arrows, other spheres and walls until told otherwise.
+    pub fn new<Marker: Component + Default>(sphere_type: SphereType) -> Self {
+        Self {
+            sphere_type,
+            material: StandardMaterial::default(),
+            collides_with: [GameLayer::ArrowSensor, GameLayer::Sphere, GameLayer::Walls].into(),
+            must_mark: false,
+            sensor: false,
+            insert: vec![Box::new(insert_marker::<Marker>)],
+        }
+    }
+    pub fn material(mut self, material: StandardMaterial) -> Self {
+        self.material = material;
+        self
+    }
+    pub fn collides_with(mut self, layers: impl Into<LayerMask>) -> Self {
+        self.collides_with = layers.into();
+        self
+    }
+    /// The sphere has to be hit before the level is cleared, instead of breaking on contact
+    pub fn must_mark(mut self) -> Self {
+        self.must_mark = true;
+        self
+    }
+    /// Arrows fly through the sphere instead of bouncing off of it
+    pub fn sensor(mut self) -> Self {
+        self.sensor = true;
+        self
+    }
+    /// Also inserts `bundle` on every sphere of this kind
+    pub fn with(mut self, bundle: impl Bundle + Clone) -> Self {
+        self.insert
+            .push(Box::new(move |sphere: &mut EntityCommands| {
+                sphere.insert(bundle.clone());
+            }));
+        self
+    }
+}
+
+fn insert_marker<Marker: Component + Default>(sphere: &mut EntityCommands) {
+    sphere.insert(Marker::default());
+}
+
+/// Every kind of sphere, looked up by [`SphereType`]
+#[derive(Resource, Default)]
+pub struct SphereKinds {
+    kinds: Vec<(SphereKind, Handle<StandardMaterial>)>,
+}
+
+impl SphereKinds {
+    /// Every sphere type, in the order they were registered
+    pub fn iter(&self) -> impl Iterator<Item = &SphereType> {
+        self.kinds.iter().map(|(kind, _)| &kind.sphere_type)
+    }
+
+    fn get(&self, sphere_type: &SphereType) -> Option<&(SphereKind, Handle<StandardMaterial>)> {
+        self.kinds
+            .iter()
+            .find(|(kind, _)| kind.sphere_type == *sphere_type)
+    }
+
+    /// Whether a kind of sphere is registered as `sphere_type`
+    pub fn contains(&self, sphere_type: &SphereType) -> bool {
+        self.get(sphere_type).is_some()
+    }
+
+    /// Swaps a name read from a level file for the registered one, so the file's copy of it can
+    /// go and the name can be written back.
+    ///
+    /// Returns false, and leaves `sphere_type` alone, if there is no such kind.
+    pub fn intern(&self, sphere_type: &mut SphereType) -> bool {
+        let Some((kind, _)) = self.get(sphere_type) else {
+            return false;
+        };
+        *sphere_type = kind.sphere_type.clone();
+        true
+    }
+
+    /// The material every sphere of `sphere_type` shares
+    pub fn material(&self, sphere_type: &SphereType) -> Option<Handle<StandardMaterial>> {
+        self.get(sphere_type).map(|(_, material)| material.clone())
+    }
+
+    /// Makes `sphere` a sphere of `sphere_type`.
+    ///
+    /// Returns false, and leaves `sphere` alone, if there is no such kind.
+    pub fn insert(&self, sphere_type: &SphereType, sphere: &mut EntityCommands) -> bool {
+        let Some((kind, material)) = self.get(sphere_type) else {
+            return false;
+        };
+
+        sphere
+            .insert_if_new(Name::new(format!("{sphere_type} Sphere")))
+            .insert((
+                MeshMaterial3d(material.clone()),
+                CollisionLayers::new(GameLayer::Sphere, kind.collides_with),
+            ));
+        if kind.must_mark {
+            sphere.insert(MustMark);
+        }
+        if kind.sensor {
+            sphere.insert(Sensor);
+        }
+        for insert in &kind.insert {
+            insert(sphere);
+        }
+        true
+    }
+
+    /// Gives `sphere` the behaviour of `sphere_type`, but keeps its own look and collisions.
+    ///
+    /// Returns false, and leaves `sphere` alone, if there is no such kind.
+    pub fn insert_behaviour(&self, sphere_type: &SphereType, sphere: &mut EntityCommands) -> bool {
+        let Some((kind, _)) = self.get(sphere_type) else {
+            return false;
+        };
+        for insert in &kind.insert {
+            insert(sphere);
+        }
+        true
+    }
+}
+
+pub(crate) trait RegisterSphereKind {
+    /// Adds a kind of sphere that levels can use by its [`SphereType`].
+    ///
+    /// Registering the same type again replaces it.
+    fn register_sphere_kind(&mut self, kind: SphereKind) -> &mut Self;
+}
+
+impl RegisterSphereKind for App {
+    fn register_sphere_kind(&mut self, kind: SphereKind) -> &mut Self {
+        let world = self.world_mut();
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(kind.material.clone());
+
+        let mut kinds = world.get_resource_or_init::<SphereKinds>();
+        kinds
+            .kinds
+            .retain(|(registered, _)| registered.sphere_type != kind.sphere_type);
+        kinds.kinds.push((kind, material));
+        self
+    }
+}
@@ -0,0 +1,72 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::gameplay::{
+    arrow::Arrow,
+    sphere::{DestroySphere, MustMark, Portal, Sphere},
+};
+
+/// How much of a heavy arrow's speed goes into the sphere it knocks
+const KNOCK_TRANSFER: f32 = 0.6;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(knock_sphere).add_observer(insert_knocked);
+}
+
+/// Sends a sphere flying instead of breaking it
+#[derive(Event)]
+pub struct KnockSphere {
+    velocity: Vec3,
+}
+
+impl KnockSphere {
+    /// Knocked by a heavy arrow flying at `velocity`
+    pub fn by_arrow(velocity: Vec3) -> Self {
+        Self {
+            velocity: velocity * KNOCK_TRANSFER,
+        }
+    }
+}
+
+/// A sphere sent flying. It breaks whatever it runs into, and itself with it.
+#[derive(Component)]
+pub struct Knocked;
+
+fn knock_sphere(
+    trigger: Trigger<KnockSphere>,
+    mut commands: Commands,
+    mut spheres: Query<&mut LinearVelocity, With<Sphere>>,
+) {
+    let Ok(mut velocity) = spheres.get_mut(trigger.target()) else {
+        return;
+    };
+    velocity.0 = trigger.event().velocity;
+    commands.entity(trigger.target()).insert(Knocked);
+}
+
+fn insert_knocked(trigger: Trigger<OnAdd, Knocked>, mut commands: Commands) {
+    commands.entity(trigger.target()).observe(break_on_contact);
+}
+
+fn break_on_contact(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    arrows: Query<(), With<Arrow>>,
+    portals: Query<(), With<Portal>>,
+    spheres: Query<(), (With<Sphere>, Without<MustMark>)>,
+    colliders: Query<&ColliderOf>,
+) {
+    let Ok(collider) = colliders.get(trigger.collider) else {
+        return;
+    };
+    let other = collider.body;
+    // portals pass it on, and the arrow that knocked it is still touching it
+    if arrows.contains(other) || portals.contains(other) {
+        return;
+    }
+    // spheres that have to be marked are marked by being run into instead
+    if spheres.contains(other) {
+        commands.trigger_targets(DestroySphere, other);
+    }
+    commands.trigger_targets(DestroySphere, trigger.target());
+}
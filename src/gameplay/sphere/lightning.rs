@@ -0,0 +1,305 @@
+use std::{collections::VecDeque, time::Duration};
+
+use avian3d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{CYAN_100, CYAN_300},
+    prelude::*,
+};
+
+use crate::{
+    gameplay::{
+        GameSet, GameState,
+        level::{LevelState, SphereType},
+        sphere::{
+            Absorber, DestroySphere, Exploder, LightFuse, Multiplier, Portal, RegisterSphereKind,
+            Sphere, SphereBroken, SphereKind,
+        },
+    },
+    rand::random_range,
+    third_party::avian3d::GameLayer,
+};
+
+/// How far lightning can jump from the sphere it starts at
+pub const LIGHTNING_RADIUS: f32 = 12.;
+/// How many of the nearest spheres a single sphere arcs to
+const LIGHTNING_TARGETS: usize = 3;
+/// The time between one strike of a chain and the next
+const STRIKE_DELAY: Duration = Duration::from_millis(150);
+/// How long a bolt stays on screen
+const BOLT_TIME: Duration = Duration::from_millis(250);
+
+/// Arcs lightning to the nearest spheres when it breaks.
+///
+/// Struck spheres react like they do to explosions, one after another. Absorbers ground the
+/// lightning, and multipliers pass it on to their own nearest spheres.
+#[derive(Component, Default)]
+#[require(Sphere)]
+pub struct Lightning;
+
+impl SphereType {
+    pub const LIGHTNING: Self = Self::new("Lightning");
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        base_color: CYAN_300.into(),
+        emissive: LinearRgba::new(0., 2., 4., 1.),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Lightning>(SphereType::LIGHTNING)
+            .material(material)
+            .collides_with([GameLayer::ArrowSensor, GameLayer::Sphere])
+            .sensor(),
+    );
+    app.add_observer(insert_lightning)
+        .add_observer(strike)
+        .add_systems(
+            Update,
+            tick_lightning
+                .in_set(GameSet::TickTimers)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (advance_chains, draw_bolts)
+                .in_set(GameSet::Update)
+                .run_if(in_state(GameState::Playing)),
+        );
+}
+
+fn insert_lightning(trigger: Trigger<OnAdd, Lightning>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .observe(super::despawn_on_arrow_collision)
+        .observe(super::despawn_on_bouncyball_collision)
+        .observe(super::despawn_on_hit_by_explosion)
+        .observe(arc_on_break);
+}
+
+/// Struck by a bolt from a lightning chain
+#[derive(Event)]
+pub struct HitByLightning {
+    /// every sphere the chain has already picked, so it never strikes one twice
+    struck: Vec<Entity>,
+}
+
+/// The spheres struck so far by the chain that struck this lightning sphere
+#[derive(Component)]
+struct StruckBy(Vec<Entity>);
+
+/// Strikes `targets` one at a time, with bolts from `from`
+#[derive(Component)]
+struct LightningChain {
+    from: Vec3,
+    targets: VecDeque<Entity>,
+    struck: Vec<Entity>,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct Bolt {
+    points: Vec<Vec3>,
+    timer: Timer,
+}
+
+impl Bolt {
+    /// A jagged line from `from` to `to`
+    fn new(from: Vec3, to: Vec3) -> Self {
+        const SEGMENTS: usize = 8;
+        const JAG: f32 = 0.6;
+
+        let across = (to - from).cross(Vec3::Z).normalize_or_zero();
+        let points = (0..=SEGMENTS)
+            .map(|segment| {
+                let point = from.lerp(to, segment as f32 / SEGMENTS as f32);
+                if segment == 0 || segment == SEGMENTS {
+                    point
+                } else {
+                    point + across * random_range(-JAG..JAG)
+                }
+            })
+            .collect();
+        Self {
+            points,
+            timer: Timer::new(BOLT_TIME, TimerMode::Once),
+        }
+    }
+}
+
+fn arc_on_break(
+    trigger: Trigger<SphereBroken>,
+    mut commands: Commands,
+    lightning: Query<(&Position, Option<&StruckBy>)>,
+    targets: Query<&Position, (With<Sphere>, Without<Portal>)>,
+    colliders: Query<&ColliderOf>,
+    spatial_query: SpatialQuery,
+) {
+    let Ok((position, struck_by)) = lightning.get(trigger.target()) else {
+        return;
+    };
+    let struck = struck_by.map_or_else(Vec::new, |struck_by| struck_by.0.clone());
+    start_chain(
+        &mut commands,
+        trigger.target(),
+        position.0,
+        struck,
+        &targets,
+        &colliders,
+        &spatial_query,
+    );
+}
+
+/// Picks the spheres nearest to `origin` that haven't been struck yet, and spawns a chain that
+/// strikes them
+fn start_chain(
+    commands: &mut Commands,
+    origin: Entity,
+    from: Vec3,
+    mut struck: Vec<Entity>,
+    targets: &Query<&Position, (With<Sphere>, Without<Portal>)>,
+    colliders: &Query<&ColliderOf>,
+    spatial_query: &SpatialQuery,
+) {
+    struck.push(origin);
+
+    let shape = Collider::sphere(LIGHTNING_RADIUS);
+    let filter = SpatialQueryFilter::from_mask(GameLayer::Sphere);
+    let mut nearest: Vec<(Entity, f32)> = spatial_query
+        .shape_intersections(&shape, from, Quat::default(), &filter)
+        .into_iter()
+        .filter_map(|hit| {
+            let body = colliders.get(hit).ok()?.body;
+            if struck.contains(&body) {
+                return None;
+            }
+            let position = targets.get(body).ok()?;
+            Some((body, position.0.distance_squared(from)))
+        })
+        .collect();
+    nearest.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    nearest.dedup_by_key(|(body, _)| *body);
+    nearest.truncate(LIGHTNING_TARGETS);
+    if nearest.is_empty() {
+        return;
+    }
+
+    let targets: VecDeque<Entity> = nearest.into_iter().map(|(body, _)| body).collect();
+    struck.extend(targets.iter().copied());
+    commands.spawn((
+        Name::new("Lightning Chain"),
+        StateScoped(LevelState::Playing),
+        LightningChain {
+            from,
+            targets,
+            struck,
+            timer: Timer::new(STRIKE_DELAY, TimerMode::Repeating),
+        },
+    ));
+}
+
+fn tick_lightning(
+    mut chains: Query<&mut LightningChain>,
+    mut bolts: Query<&mut Bolt>,
+    time: Res<Time>,
+) {
+    for mut chain in &mut chains {
+        chain.timer.tick(time.delta());
+    }
+    for mut bolt in &mut bolts {
+        bolt.timer.tick(time.delta());
+    }
+}
+
+fn advance_chains(
+    mut commands: Commands,
+    mut chains: Query<(Entity, &mut LightningChain)>,
+    positions: Query<&Position>,
+) {
+    for (entity, mut chain) in &mut chains {
+        if !chain.timer.just_finished() {
+            continue;
+        }
+        // spheres that broke since the chain started are skipped
+        while let Some(target) = chain.targets.pop_front() {
+            let Ok(position) = positions.get(target) else {
+                continue;
+            };
+            commands.spawn((
+                Name::new("Lightning Bolt"),
+                StateScoped(LevelState::Playing),
+                Bolt::new(chain.from, position.0),
+            ));
+            commands.trigger_targets(
+                HitByLightning {
+                    struck: chain.struck.clone(),
+                },
+                target,
+            );
+            break;
+        }
+        if chain.targets.is_empty() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn strike(
+    trigger: Trigger<HitByLightning>,
+    mut commands: Commands,
+    kinds: Query<(
+        Has<Absorber>,
+        Has<Exploder>,
+        Has<Multiplier>,
+        Has<Lightning>,
+    )>,
+    targets: Query<&Position, (With<Sphere>, Without<Portal>)>,
+    colliders: Query<&ColliderOf>,
+    spatial_query: SpatialQuery,
+) {
+    let sphere = trigger.target();
+    let Ok((absorber, exploder, multiplier, lightning)) = kinds.get(sphere) else {
+        return;
+    };
+
+    // absorbers ground the lightning, and it stops there. Full ones break.
+    if absorber {
+        commands.trigger_targets(DestroySphere, sphere);
+        return;
+    }
+    // light a much smaller fuse, like an explosion does
+    if exploder {
+        commands.trigger_targets(LightFuse::Chained, sphere);
+        return;
+    }
+    let struck = trigger.event().struck.clone();
+    if multiplier {
+        // multipliers pass the lightning on to their own nearest spheres
+        if let Ok(position) = targets.get(sphere) {
+            start_chain(
+                &mut commands,
+                sphere,
+                position.0,
+                struck,
+                &targets,
+                &colliders,
+                &spatial_query,
+            );
+        }
+    } else if lightning {
+        // picked up by `arc_on_break` once it breaks
+        commands.entity(sphere).insert(StruckBy(struck));
+    }
+    commands.trigger_targets(DestroySphere, sphere);
+}
+
+fn draw_bolts(mut commands: Commands, bolts: Query<(Entity, &Bolt)>, mut gizmos: Gizmos) {
+    for (entity, bolt) in &bolts {
+        if bolt.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let color = Color::from(CYAN_100).with_alpha(bolt.timer.fraction_remaining());
+        gizmos.linestrip(bolt.points.iter().copied(), color);
+    }
+}
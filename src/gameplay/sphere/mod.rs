@@ -1,13 +1,10 @@
 use std::path::Path;
 
 use avian3d::prelude::*;
-use bevy::{
-    color::palettes::{
-        css::{GREEN, ORANGE, YELLOW},
-        tailwind::BLUE_400,
-    },
-    prelude::*,
-};
+use bevy::prelude::*;
+
+mod kind;
+pub use kind::*;
 
 mod normal;
 pub use normal::*;
@@ -30,12 +27,27 @@ pub use destroy::*;
 mod gravity;
 pub use gravity::*;
 
+mod force_field;
+pub use force_field::*;
+
 mod absorber;
 pub use absorber::*;
 
+mod portal;
+pub use portal::*;
+
+mod lightning;
+pub use lightning::*;
+
+mod knock;
+pub use knock::*;
+
+mod rules;
+pub use rules::*;
+
 use crate::{
     asset_tracking::LoadResource,
-    gameplay::arrow::{Arrow, NockedOn},
+    gameplay::arrow::{Arrow, ArrowKind, NockedOn},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -48,6 +60,11 @@ pub(super) fn plugin(app: &mut App) {
         bouncy::plugin,
         destroy::plugin,
         gravity::plugin,
+        force_field::plugin,
+        portal::plugin,
+        lightning::plugin,
+        knock::plugin,
+        rules::plugin,
     ));
 
     app.register_type::<SphereAssets>()
@@ -64,105 +81,27 @@ pub struct SphereAssets {
     #[dependency]
     pub gibs: Handle<Scene>,
     pub break_sfx: Handle<AudioSource>,
-    pub normal: Handle<StandardMaterial>,
-    pub multiplier: Handle<StandardMaterial>,
-    pub time_freeze: Handle<StandardMaterial>,
-    pub absorber: Handle<StandardMaterial>,
-    pub bouncy: Handle<StandardMaterial>,
-    pub gravity: Handle<StandardMaterial>,
-    pub exploder: Handle<StandardMaterial>,
 }
 
 impl FromWorld for SphereAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
-        let model = assets.load("models/sph.glb#Scene0");
-        let mesh = assets.load("models/sph.glb#Mesh0/Primitive0");
-        let gibs = assets.load("models/glass_fractured.glb#Scene0");
-        let break_sfx = assets.load(Path::new("audio/sfx/GlassBreakSFX.flac"));
-        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
-
-        let base = StandardMaterial {
-            base_color: Color::srgb(0.7, 0.7, 1.0),
-            // specular_tint: Color::from(Srgba::RED),
-            reflectance: 1.,
-            specular_transmission: 0.90,
-            diffuse_transmission: 0.5,
-            thickness: 0.6,
-            ior: 1.5,
-            perceptual_roughness: 0.4,
-            ..Default::default()
-        };
-
-        let normal = materials.add(base.clone());
-
-        let multiplier = materials.add(StandardMaterial {
-            base_color: ORANGE.into(),
-            ..default()
-        });
-
-        let time_freeze = materials.add(StandardMaterial {
-            base_color: BLUE_400.into(),
-            emissive: LinearRgba::new(0.0, 0., 1., 1.),
-            ..default()
-        });
-
-        let absorber = materials.add(StandardMaterial {
-            base_color: GREEN.into(),
-            reflectance: 1.,
-            specular_transmission: 0.90,
-            diffuse_transmission: 0.5,
-            thickness: 0.6,
-            ior: 1.5,
-            perceptual_roughness: 0.4,
-            ..default()
-        });
-
-        let bouncy = materials.add(StandardMaterial {
-            base_color: YELLOW.into(),
-            ..default()
-        });
-
-        let gravity = materials.add(StandardMaterial {
-            base_color: Color::BLACK,
-            emissive: Color::BLACK.into(),
-            unlit: true,
-            // specular_tint: Color::from(Srgba::RED),
-            reflectance: 0.,
-            metallic: 0.,
-            specular_transmission: 0.0,
-            diffuse_transmission: 0.0,
-            thickness: 5.,
-            ior: 1.,
-            perceptual_roughness: 1.0,
-            ..default()
-        });
-
-        let exploder = materials.add(StandardMaterial {
-            //base_color: RED.into(),
-            emissive: Srgba::rgb(5., 0., 0.).into(),
-            ..default()
-        });
         Self {
-            model,
-            mesh,
-            gibs,
-            break_sfx,
-            normal,
-            absorber,
-            multiplier,
-            time_freeze,
-            bouncy,
-            gravity,
-            exploder,
+            model: assets.load("models/sph.glb#Scene0"),
+            mesh: assets.load("models/sph.glb#Mesh0/Primitive0"),
+            gibs: assets.load("models/glass_fractured.glb#Scene0"),
+            break_sfx: assets.load(Path::new("audio/sfx/GlassBreakSFX.flac")),
         }
     }
 }
 
+/// The radius of every sphere's collider
+pub const SPHERE_RADIUS: f32 = 1.;
+
 #[derive(Component, Default)]
 #[require(RigidBody = RigidBody::Dynamic)]
 #[require(LockedAxes = LockedAxes::ROTATION_LOCKED.lock_translation_z())]
-#[require(Collider = Collider::sphere(1.))]
+#[require(Collider = Collider::sphere(SPHERE_RADIUS))]
 #[require(CollisionEventsEnabled)]
 pub struct Sphere;
 
@@ -240,7 +179,8 @@ fn despawn_on_arrow_collision(
     trigger: Trigger<OnCollisionStart>,
     mut commands: Commands,
     absorbers: Query<(), With<Absorber>>,
-    arrows: Query<(), (With<Arrow>, Without<NockedOn>)>,
+    arrows: Query<(&ArrowKind, &LinearVelocity), (With<Arrow>, Without<NockedOn>)>,
+    sensors: Query<(), With<Sensor>>,
     colliders: Query<&ColliderOf>,
 ) {
     if absorbers.get(trigger.target()).is_ok() {
@@ -251,16 +191,21 @@ fn despawn_on_arrow_collision(
     let Ok(collider) = colliders.get(event.collider) else {
         return;
     };
-    if arrows.get(collider.body).is_err() {
+    let Ok((kind, velocity)) = arrows.get(collider.body) else {
         return;
-    }
+    };
     let Ok(sphere_collider) = colliders.get(trigger.target()) else {
         return;
     };
     let Ok(mut entity) = commands.get_entity(sphere_collider.body) else {
         return;
     };
-    entity.trigger(DestroySphere);
+    // spheres arrows fly through don't stop at walls either, so they shatter as usual
+    if *kind == ArrowKind::Heavy && !sensors.contains(sphere_collider.body) {
+        entity.trigger(KnockSphere::by_arrow(velocity.0));
+    } else {
+        entity.trigger(DestroySphere);
+    }
 }
 
 fn despawn_on_bouncyball_collision(
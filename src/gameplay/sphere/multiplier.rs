@@ -1,18 +1,35 @@
-use std::time::Duration;
+use std::{f32::consts::PI, time::Duration};
 
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color::palettes::css::ORANGE, prelude::*};
 
 use super::Sphere;
 use crate::{
     gameplay::{
         GameSet,
-        sphere::{Absorber, DestroySphere, Exploder, HitByExplosion, LightFuse, SphereAssets},
+        level::SphereType,
+        sphere::{
+            Absorber, DestroySphere, HitByExplosion, LightFuse, RegisterSphereKind, SphereKind,
+        },
     },
     third_party::avian3d::GameLayer,
 };
 
+impl SphereType {
+    pub const MULTIPLIER: Self = Self::new("Multiplier");
+}
+
 pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        base_color: ORANGE.into(),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Multiplier>(SphereType::MULTIPLIER)
+            .material(material)
+            .collides_with([GameLayer::ArrowSensor, GameLayer::Sphere])
+            .sensor(),
+    );
     app.add_observer(insert_multiplier)
         .add_systems(
             Update,
@@ -35,29 +52,16 @@ pub(super) fn plugin(app: &mut App) {
         );
 }
 
+/// How far the copies of an arrow or ball that hits a multiplier turn away from it, 35 degrees
+pub const SPLIT_ANGLE: f32 = 35. * PI / 180.;
+
 #[derive(Component, Default)]
 #[require(Sphere)]
 pub struct Multiplier;
 
-fn insert_multiplier(
-    trigger: Trigger<OnAdd, Multiplier>,
-    absorbers: Query<(), With<Absorber>>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
-    let mut commands = commands.entity(trigger.target());
-
-    if absorbers.get(trigger.target()).is_err() {
-        commands.insert((
-            MeshMaterial3d(assets.multiplier.clone()),
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::ArrowSensor, GameLayer::Sphere],
-            ),
-        ));
-    }
-
+fn insert_multiplier(trigger: Trigger<OnAdd, Multiplier>, mut commands: Commands) {
     commands
+        .entity(trigger.target())
         .observe(super::despawn_on_arrow_collision)
         .observe(super::despawn_on_bouncyball_collision)
         .observe(multiply_collider_on_hit)
@@ -71,6 +75,10 @@ impl FromAbsorberMultiply {
     pub fn forever() -> Self {
         Self(Timer::new(Duration::MAX, TimerMode::Once))
     }
+    /// Starts the cooldown over
+    pub fn restart(&mut self) {
+        self.0.reset();
+    }
 }
 impl Default for FromAbsorberMultiply {
     fn default() -> Self {
@@ -113,12 +121,11 @@ fn multiply_explosion(
         commands
             .spawn((
                 Name::new("Exploder Replica"),
-                Exploder,
-                Sensor,
+                SphereType::EXPLODER,
                 FromAbsorberMultiply::forever(),
                 transform,
             ))
-            .trigger(LightFuse(3));
+            .trigger(LightFuse::Hit);
     }
     let Ok(mut entity) = commands.get_entity(trigger.target()) else {
         return;
@@ -182,7 +189,7 @@ fn multiply_collider_on_hit(
         ShouldMultiply {
             is_from_absorber,
             local_point: hit_trns.translation() + local_point,
-            rot_offset: vec![35.0_f32.to_radians(), -35.0_f32.to_radians()],
+            rot_offset: vec![SPLIT_ANGLE, -SPLIT_ANGLE],
         },
         collider.body,
     );
@@ -1,30 +1,37 @@
-use avian3d::prelude::*;
 use bevy::prelude::*;
 
 use super::Sphere;
-use crate::{gameplay::sphere::SphereAssets, third_party::avian3d::GameLayer};
+use crate::gameplay::{
+    level::SphereType,
+    sphere::{RegisterSphereKind, SphereKind},
+};
 
 #[derive(Component, Default)]
 #[require(Sphere)]
 pub struct Normal;
 
+impl SphereType {
+    pub const NORMAL: Self = Self::new("Normal");
+}
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(insert_normal);
+    let material = StandardMaterial {
+        base_color: Color::srgb(0.7, 0.7, 1.0),
+        // specular_tint: Color::from(Srgba::RED),
+        reflectance: 1.,
+        specular_transmission: 0.90,
+        diffuse_transmission: 0.5,
+        thickness: 0.6,
+        ior: 1.5,
+        perceptual_roughness: 0.4,
+        ..Default::default()
+    };
+    app.register_sphere_kind(SphereKind::new::<Normal>(SphereType::NORMAL).material(material))
+        .add_observer(insert_normal);
 }
-fn insert_normal(
-    trigger: Trigger<OnAdd, Normal>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
+fn insert_normal(trigger: Trigger<OnAdd, Normal>, mut commands: Commands) {
     commands
         .entity(trigger.target())
-        .insert((
-            MeshMaterial3d(assets.normal.clone()),
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::ArrowSensor, GameLayer::Sphere, GameLayer::Walls],
-            ),
-        ))
         .observe(super::despawn_on_arrow_collision)
         .observe(super::despawn_on_bouncyball_collision)
         .observe(super::despawn_on_hit_by_explosion);
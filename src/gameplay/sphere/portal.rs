@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{VIOLET_200, VIOLET_500},
+    prelude::*,
+};
+
+use crate::{
+    gameplay::{
+        GameSet,
+        arrow::{Arrow, NockedOn},
+        level::{PortalLink, SphereType},
+        sphere::{
+            Bouncy, FromAbsorberMultiply, RegisterSphereKind, SPHERE_RADIUS, Sphere, SphereKind,
+        },
+    },
+    third_party::avian3d::GameLayer,
+};
+
+/// How long something that came out of a portal can't go into another one
+const PORTAL_COOLDOWN: Duration = Duration::from_millis(300);
+
+/// Sends arrows and bouncy balls to the other portal of its [`PortalLink`].
+///
+/// Portals never break, and don't have to be hit to clear a level.
+#[derive(Component, Default)]
+#[require(Sphere)]
+pub struct Portal;
+
+impl SphereType {
+    pub const PORTAL: Self = Self::new("Portal");
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let material = StandardMaterial {
+        base_color: VIOLET_500.into(),
+        emissive: LinearRgba::new(0.5, 0., 1., 1.),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<Portal>(SphereType::PORTAL)
+            .material(material)
+            .collides_with([GameLayer::ArrowSensor, GameLayer::Sphere])
+            .sensor()
+            // nothing pushes portals around
+            .with(RigidBody::Static),
+    );
+    app.init_resource::<PortalAssets>();
+    app.add_observer(insert_portal)
+        .add_observer(add_facing_indicator)
+        .add_systems(
+            Update,
+            (|mut cooldowns: Query<&mut Teleported>, time: Res<Time>| {
+                for mut cooldown in &mut cooldowns {
+                    cooldown.0.tick(time.delta());
+                }
+            })
+            .in_set(GameSet::TickTimers),
+        )
+        .add_systems(
+            PostUpdate,
+            |mut commands: Commands, cooldowns: Query<(Entity, &Teleported)>| {
+                for (entity, cooldown) in cooldowns {
+                    if cooldown.0.finished() {
+                        commands.entity(entity).remove::<Teleported>();
+                    }
+                }
+            },
+        );
+}
+
+#[derive(Resource)]
+struct PortalAssets {
+    cone: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+impl FromWorld for PortalAssets {
+    fn from_world(world: &mut World) -> Self {
+        let cone = world.resource_mut::<Assets<Mesh>>().add(Cone {
+            radius: 0.3,
+            height: 0.6,
+        });
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: VIOLET_200.into(),
+                emissive: LinearRgba::new(0.5, 0.3, 1., 1.),
+                ..default()
+            });
+
+        Self { cone, material }
+    }
+}
+
+/// Came out of a portal a moment ago
+#[derive(Component)]
+struct Teleported(Timer);
+
+impl Default for Teleported {
+    fn default() -> Self {
+        Self(Timer::new(PORTAL_COOLDOWN, TimerMode::Once))
+    }
+}
+
+fn insert_portal(trigger: Trigger<OnAdd, Portal>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .observe(teleport_on_collision);
+}
+
+// a small cone on the side things come out of
+fn add_facing_indicator(
+    trigger: Trigger<OnAdd, PortalLink>,
+    mut commands: Commands,
+    links: Query<&PortalLink>,
+    assets: Res<PortalAssets>,
+) {
+    let Ok(link) = links.get(trigger.target()) else {
+        return;
+    };
+    let facing = link.facing.normalize_or(Vec2::Y).extend(0.);
+
+    let indicator = commands
+        .spawn((
+            Name::new("Portal Facing"),
+            Mesh3d(assets.cone.clone()),
+            MeshMaterial3d(assets.material.clone()),
+            Transform::from_translation(facing * (SPHERE_RADIUS + 0.3))
+                .with_rotation(Quat::from_rotation_arc(Vec3::Y, facing)),
+        ))
+        .id();
+    commands.entity(trigger.target()).add_child(indicator);
+}
+
+fn teleport_on_collision(
+    trigger: Trigger<OnCollisionStart>,
+    mut commands: Commands,
+    colliders: Query<&ColliderOf>,
+    portals: Query<(Entity, &PortalLink, &Position), With<Portal>>,
+    mut travellers: Query<
+        (
+            &mut Position,
+            &mut Rotation,
+            &mut LinearVelocity,
+            Has<Arrow>,
+            Option<&mut FromAbsorberMultiply>,
+        ),
+        (
+            Or<(With<Arrow>, With<Bouncy>)>,
+            Without<NockedOn>,
+            Without<Teleported>,
+            Without<Portal>,
+        ),
+    >,
+) {
+    let Ok(collider) = colliders.get(trigger.collider) else {
+        return;
+    };
+    let Ok((mut position, mut rotation, mut velocity, is_arrow, multiplied)) =
+        travellers.get_mut(collider.body)
+    else {
+        return;
+    };
+    let Ok((entrance, entrance_link, _)) = portals.get(trigger.target()) else {
+        return;
+    };
+    let Some((_, exit_link, exit_position)) = portals
+        .iter()
+        .find(|(portal, link, _)| *portal != entrance && link.pair == entrance_link.pair)
+    else {
+        return;
+    };
+
+    // going into the entrance's face is coming out of the exit's face
+    let turn = exit_link.facing.to_angle() - (-entrance_link.facing).to_angle();
+    let new_velocity = Vec2::from_angle(turn).rotate(velocity.0.xy());
+    let direction = new_velocity
+        .try_normalize()
+        .unwrap_or(exit_link.facing.normalize_or(Vec2::Y));
+
+    velocity.0 = new_velocity.extend(velocity.0.z);
+    position.0 = (exit_position.0.xy() + direction * SPHERE_RADIUS).extend(position.0.z);
+    if is_arrow {
+        // Arrow points along +Y axis in its local space
+        rotation.0 = Quat::from_rotation_arc(Vec3::Y, direction.extend(0.));
+    }
+    // replicas coming out of a portal still can't be multiplied again straight away
+    if let Some(mut multiplied) = multiplied {
+        multiplied.restart();
+    }
+
+    commands.entity(collider.body).insert(Teleported::default());
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+
+use bevy::{
+    color::palettes::tailwind::{GRAY_900, RED_600},
+    prelude::{Val::*, *},
+};
+use bevy_mod_outline::OutlineVolume;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    camera::WorldCamera,
+    gameplay::{
+        level::{LevelState, RebuildLevel},
+        scorecard::ArrowCountsTowardsScore,
+        sphere::{MarkedForDeletion, SphereBroken},
+    },
+};
+
+/// Has to be broken after every sphere with a lower number.
+///
+/// Spheres with the same number can be broken in any order between themselves.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BreakOrder(pub u32);
+
+/// A sphere that shouldn't be broken. It doesn't have to be broken to clear the level.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hazard {
+    /// breaking it costs a stroke
+    Penalty,
+    /// breaking it fails the level
+    Fail,
+}
+
+/// Triggered when the player breaks one of the level's rules
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleBroken {
+    Hazard(Hazard),
+    /// broke sphere number `broke` while number `before` was still there
+    OutOfOrder {
+        broke: u32,
+        before: u32,
+    },
+}
+
+impl RuleBroken {
+    pub fn fails_level(&self) -> bool {
+        *self != Self::Hazard(Hazard::Penalty)
+    }
+}
+
+impl fmt::Display for RuleBroken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hazard(Hazard::Penalty) => f.write_str("Broke a hazard, +1 stroke"),
+            Self::Hazard(Hazard::Fail) => f.write_str("Broke a hazard!"),
+            Self::OutOfOrder { broke, before } => write!(f, "Broke {broke} before {before}!"),
+        }
+    }
+}
+
+/// The rules broken since the level was last set up
+#[derive(Resource, Default, Debug)]
+pub struct BrokenRules {
+    pub penalties: u32,
+    pub failed: bool,
+    /// ordered spheres broken this frame. They are checked once everything that breaks together
+    /// has broken, so one explosion can take out several numbers at once.
+    just_broken: Vec<u32>,
+}
+
+impl BrokenRules {
+    pub fn any(&self) -> bool {
+        self.penalties > 0 || self.failed
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<BrokenRules>()
+        .add_systems(OnEnter(LevelState::NewLevel), reset_broken_rules)
+        .add_observer(|_: Trigger<RebuildLevel>, rules: ResMut<BrokenRules>| {
+            reset_broken_rules(rules);
+        })
+        .add_observer(outline_hazard)
+        .add_observer(add_order_label)
+        .add_observer(check_broken_sphere)
+        .add_observer(check_marked_sphere)
+        .add_observer(count_broken_rule)
+        .add_systems(PostUpdate, (check_break_order, place_order_labels));
+}
+
+fn reset_broken_rules(mut rules: ResMut<BrokenRules>) {
+    *rules = default();
+}
+
+fn check_broken_sphere(
+    trigger: Trigger<SphereBroken>,
+    mut commands: Commands,
+    spheres: Query<(Option<&Hazard>, Option<&BreakOrder>), Without<MarkedForDeletion>>,
+    mut rules: ResMut<BrokenRules>,
+) {
+    // spheres that must be marked were already checked when they were marked
+    if let Ok((hazard, order)) = spheres.get(trigger.target()) {
+        break_sphere(&mut commands, hazard, order, &mut rules);
+    }
+}
+
+fn check_marked_sphere(
+    trigger: Trigger<OnAdd, MarkedForDeletion>,
+    mut commands: Commands,
+    spheres: Query<(Option<&Hazard>, Option<&BreakOrder>)>,
+    mut rules: ResMut<BrokenRules>,
+) {
+    if let Ok((hazard, order)) = spheres.get(trigger.target()) {
+        break_sphere(&mut commands, hazard, order, &mut rules);
+    }
+}
+
+fn break_sphere(
+    commands: &mut Commands,
+    hazard: Option<&Hazard>,
+    order: Option<&BreakOrder>,
+    rules: &mut BrokenRules,
+) {
+    if let Some(hazard) = hazard {
+        commands.trigger(RuleBroken::Hazard(*hazard));
+    }
+    if let Some(order) = order {
+        rules.just_broken.push(order.0);
+    }
+}
+
+fn check_break_order(
+    mut commands: Commands,
+    mut rules: ResMut<BrokenRules>,
+    remaining: Query<&BreakOrder, Without<MarkedForDeletion>>,
+) {
+    if rules.just_broken.is_empty() {
+        return;
+    }
+    let Some(lowest) = remaining.iter().min() else {
+        rules.just_broken.clear();
+        return;
+    };
+    if let Some(broke) = rules
+        .just_broken
+        .iter()
+        .copied()
+        .find(|broke| *broke > lowest.0)
+    {
+        commands.trigger(RuleBroken::OutOfOrder {
+            broke,
+            before: lowest.0,
+        });
+    }
+    rules.just_broken.clear();
+}
+
+fn count_broken_rule(
+    trigger: Trigger<RuleBroken>,
+    mut commands: Commands,
+    mut rules: ResMut<BrokenRules>,
+) {
+    if trigger.event().fails_level() {
+        rules.failed = true;
+    } else {
+        rules.penalties += 1;
+        commands.trigger(ArrowCountsTowardsScore);
+    }
+}
+
+fn outline_hazard(trigger: Trigger<OnAdd, Hazard>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(OutlineVolume {
+        visible: true,
+        colour: RED_600.into(),
+        width: 1.,
+    });
+}
+
+/// The number of an ordered sphere, kept over it on screen
+#[derive(Component)]
+struct OrderLabel(Entity);
+
+const ORDER_LABEL_SIZE: f32 = 24.;
+
+fn add_order_label(
+    trigger: Trigger<OnAdd, BreakOrder>,
+    mut commands: Commands,
+    orders: Query<&BreakOrder>,
+) {
+    let Ok(order) = orders.get(trigger.target()) else {
+        return;
+    };
+    commands.spawn((
+        Name::new("Order Label"),
+        OrderLabel(trigger.target()),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Px(ORDER_LABEL_SIZE),
+            height: Px(ORDER_LABEL_SIZE),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(GRAY_900.with_alpha(0.7).into()),
+        BorderRadius::MAX,
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        children![(
+            Text::new(order.0.to_string()),
+            TextColor(Color::WHITE),
+            TextFont::from_font_size(16.),
+        )],
+    ));
+}
+
+fn place_order_labels(
+    mut commands: Commands,
+    mut labels: Query<(Entity, &OrderLabel, &mut Node, &mut Visibility)>,
+    spheres: Query<&GlobalTransform, (With<BreakOrder>, Without<MarkedForDeletion>)>,
+    camera: Single<(&Camera, &GlobalTransform), With<WorldCamera>>,
+) {
+    let (camera, camera_transform) = *camera;
+    for (label, OrderLabel(sphere), mut node, mut visibility) in &mut labels {
+        // broken spheres are disabled, and don't show up here anymore
+        let Ok(sphere) = spheres.get(*sphere) else {
+            commands.entity(label).despawn();
+            continue;
+        };
+        let Ok(position) = camera.world_to_viewport(camera_transform, sphere.translation()) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        node.left = Px(position.x - ORDER_LABEL_SIZE / 2.);
+        node.top = Px(position.y - ORDER_LABEL_SIZE / 2.);
+        *visibility = Visibility::Inherited;
+    }
+}
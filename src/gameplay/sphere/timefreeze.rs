@@ -1,10 +1,11 @@
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{color::palettes::tailwind::BLUE_400, prelude::*};
 
 use crate::{
     gameplay::{
         arrow::{Arrow, Canceled, NockedOn},
-        sphere::{Sphere, SphereAssets},
+        level::SphereType,
+        sphere::{RegisterSphereKind, Sphere, SphereKind},
         timefreeze::FreezeTime,
     },
     third_party::avian3d::GameLayer,
@@ -15,23 +16,27 @@ use crate::{
 #[require(Sphere)]
 pub struct TimeFreeze;
 
+impl SphereType {
+    pub const TIME_FREEZE: Self = Self::new("TimeFreeze");
+}
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(insert_timefreeze);
+    let material = StandardMaterial {
+        base_color: BLUE_400.into(),
+        emissive: LinearRgba::new(0.0, 0., 1., 1.),
+        ..default()
+    };
+    app.register_sphere_kind(
+        SphereKind::new::<TimeFreeze>(SphereType::TIME_FREEZE)
+            .material(material)
+            .collides_with([GameLayer::ArrowSensor, GameLayer::Sphere])
+            .sensor(),
+    )
+    .add_observer(insert_timefreeze);
 }
-fn insert_timefreeze(
-    trigger: Trigger<OnAdd, TimeFreeze>,
-    mut commands: Commands,
-    assets: Res<SphereAssets>,
-) {
+fn insert_timefreeze(trigger: Trigger<OnAdd, TimeFreeze>, mut commands: Commands) {
     commands
         .entity(trigger.target())
-        .insert((
-            CollisionLayers::new(
-                GameLayer::Sphere,
-                [GameLayer::ArrowSensor, GameLayer::Sphere],
-            ),
-            MeshMaterial3d(assets.time_freeze.clone()),
-        ))
         .observe(freeze_on_arrow_collision);
 }
 
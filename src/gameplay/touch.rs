@@ -0,0 +1,162 @@
+//! Plays the game on a touch screen.
+//!
+//! Touching the level puts the bow down under the finger and starts drawing it, dragging pulls the
+//! string back and lifting the finger fires. A second finger cancels the shot, and pinching with
+//! two fingers zooms in on the level around them. The HUD has touch-sized buttons for the rest.
+
+use bevy::prelude::*;
+
+use crate::{
+    Screen,
+    camera::{WORLD_CAMERA_FOV, WorldCamera},
+    gameplay::{
+        GameSet, GameState,
+        cursor::{CursorPosition, PointerSource, viewport_to_plane},
+    },
+    world::GAME_PLANE,
+};
+
+/// How far in a pinch can zoom, as a multiple of the whole level
+const MAX_ZOOM: f32 = 3.;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DrawingFinger>()
+        .add_systems(
+            Update,
+            (track_touches, pinch_to_zoom)
+                .chain()
+                .in_set(GameSet::RecordInput)
+                .run_if(not(in_state(GameState::Paused))),
+        )
+        .add_systems(OnExit(Screen::Gameplay), reset_zoom);
+}
+
+/// The finger drawing the bow, if there is one
+#[derive(Resource, Default)]
+pub struct DrawingFinger(Option<u64>);
+
+/// Whether the drawing finger just touched down
+pub fn finger_down(finger: Res<DrawingFinger>, touches: Res<Touches>) -> bool {
+    finger.0.is_some_and(|id| touches.just_pressed(id))
+}
+
+/// Whether the drawing finger was just lifted
+pub fn finger_lifted(finger: Res<DrawingFinger>, touches: Res<Touches>) -> bool {
+    finger.0.is_some_and(|id| touches.just_released(id))
+}
+
+/// Whether another finger just joined the drawing one, or the device took the touch back
+pub fn finger_canceled(finger: Res<DrawingFinger>, touches: Res<Touches>) -> bool {
+    finger.0.is_some_and(|id| {
+        touches.just_canceled(id) || touches.iter_just_pressed().any(|touch| touch.id() != id)
+    })
+}
+
+/// Picks the finger that draws the bow and moves the cursor along with it.
+///
+/// Runs before anything that reads the finger's conditions.
+pub(super) fn track_touches(
+    touches: Res<Touches>,
+    buttons: Query<&Interaction, With<Button>>,
+    camera: Query<(&Camera, &GlobalTransform), With<WorldCamera>>,
+    mut finger: ResMut<DrawingFinger>,
+    mut source: ResMut<PointerSource>,
+    mut cursor: ResMut<CursorPosition>,
+) {
+    // the finger is kept for the frame it is lifted or joined, so the shot can react to it
+    if let Some(id) = finger.0 {
+        let lifted = touches.get_pressed(id).is_none()
+            && !touches.just_released(id)
+            && !touches.just_canceled(id);
+        let joined = touches.iter().any(|touch| touch.id() != id)
+            && !touches.iter_just_pressed().any(|touch| touch.id() != id);
+        if lifted || joined {
+            finger.0 = None;
+        }
+    }
+
+    if touches.iter_just_pressed().next().is_some() {
+        source.set_if_neq(PointerSource::Touch);
+    }
+    // buttons in the HUD take their own touches
+    let on_button = buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    if finger.0.is_none() && touches.iter().count() == 1 && !on_button {
+        finger.0 = touches.iter_just_pressed().next().map(|touch| touch.id());
+    }
+
+    let Some(id) = finger.0 else {
+        return;
+    };
+    let Some(touch) = touches.get_pressed(id).or(touches.get_released(id)) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera.single() else {
+        return;
+    };
+    if let Some(point) = viewport_to_plane(camera, camera_transform, touch.position()) {
+        source.set_if_neq(PointerSource::Touch);
+        cursor.set(point);
+    }
+}
+
+fn pinch_to_zoom(
+    touches: Res<Touches>,
+    finger: Res<DrawingFinger>,
+    camera: Single<(&Camera, &GlobalTransform, &mut Transform, &mut Projection), With<WorldCamera>>,
+) {
+    if finger.0.is_some() {
+        return;
+    }
+    let mut pinch = touches.iter();
+    let (Some(first), Some(second), None) = (pinch.next(), pinch.next(), pinch.next()) else {
+        return;
+    };
+    let before = first
+        .previous_position()
+        .distance(second.previous_position());
+    let after = first.position().distance(second.position());
+    if before <= 0. || after <= 0. {
+        return;
+    }
+
+    let (camera, camera_transform, mut transform, mut projection) = camera.into_inner();
+    let Projection::Perspective(perspective) = &mut *projection else {
+        return;
+    };
+    let full = (WORLD_CAMERA_FOV / 2.).tan();
+    let current = (perspective.fov / 2.).tan();
+    // spreading the fingers apart zooms in
+    let zoomed = (current * before / after).clamp(full / MAX_ZOOM, full);
+    let scale = current / zoomed;
+    perspective.fov = zoomed.atan() * 2.;
+
+    // the point between the fingers stays under them
+    let center = transform.translation.xy();
+    let middle = (first.position() + second.position()) / 2.;
+    let Some(pinched) = viewport_to_plane(camera, camera_transform, middle) else {
+        return;
+    };
+    let mut center = pinched + (center - pinched) / scale;
+
+    // never show anything past the edges of the whole level
+    if let Some(size) = camera.logical_viewport_size() {
+        let half_height = full * (transform.translation.z - GAME_PLANE);
+        let half_view = Vec2::new(half_height * size.x / size.y, half_height);
+        let room = half_view * (1. - zoomed / full);
+        center = center.clamp(-room, room);
+    }
+    transform.translation.x = center.x;
+    transform.translation.y = center.y;
+}
+
+/// Undoes pinching, so the camera is back over the middle of the level and sees all of it
+fn reset_zoom(camera: Single<(&mut Transform, &mut Projection), With<WorldCamera>>) {
+    let (mut transform, mut projection) = camera.into_inner();
+    if let Projection::Perspective(perspective) = &mut *projection {
+        perspective.fov = WORLD_CAMERA_FOV;
+    }
+    transform.translation.x = 0.;
+    transform.translation.y = 0.;
+}
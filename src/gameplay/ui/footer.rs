@@ -4,7 +4,7 @@ use bevy::color::palettes::{
 };
 
 use crate::{
-    gameplay::{level::Level, mulligan::Mulligan, scorecard::ScoreCard},
+    gameplay::{level::Level, mulligan::Mulligan, quiver::Quiver, scorecard::ScoreCard},
     settings::Settings,
 };
 
@@ -20,6 +20,7 @@ pub(super) fn plugin(app: &mut App) {
                 update_mulligan_keybind.run_if(resource_changed::<Settings>),
                 update_ui_playing_course_score_count
                     .run_if(resource_changed::<ScoreCard>.or(resource_changed::<Level>)),
+                update_quiver_ui.run_if(resource_changed::<Quiver>),
             ),
         );
 }
@@ -83,7 +84,7 @@ pub fn footer(settings: Res<Settings>) -> impl Bundle {
         },
         Pickable::IGNORE,
         Footer,
-        children![arrowcount_node(), mulligan(settings)],
+        children![arrowcount_node(), quiver(), mulligan(settings)],
     )
 }
 
@@ -239,3 +240,71 @@ fn mulligan(settings: Res<Settings>) -> impl Bundle {
         )],
     )
 }
+
+#[derive(Component)]
+pub struct UiQuiver;
+
+/// Holds one line for each kind of arrow in the quiver
+#[derive(Component)]
+pub struct UiQuiverSlots;
+
+/// Only shown on levels that hand out special arrows
+fn quiver() -> impl Bundle {
+    (
+        ui_box(),
+        UiQuiver,
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (Text::new("Quiver"), TextColor(GRAY_700.into())),
+                (
+                    UiQuiverSlots,
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Start,
+                        ..default()
+                    },
+                )
+            ],
+        )],
+    )
+}
+
+fn update_quiver_ui(
+    mut commands: Commands,
+    mut ui: Single<&mut Node, With<UiQuiver>>,
+    slots: Single<Entity, With<UiQuiverSlots>>,
+    quiver: Res<Quiver>,
+) {
+    if !quiver.has_special() {
+        ui.display = Display::None;
+        return;
+    }
+    ui.display = Display::Flex;
+
+    commands.entity(*slots).despawn_related::<Children>();
+    for (index, (kind, left)) in quiver.slots().enumerate() {
+        let mut line = format!("[{}] {kind}", index + 1);
+        if let Some(left) = left {
+            line.push_str(&format!(" x{left}"));
+        }
+        let color = if kind == quiver.selected() {
+            Color::BLACK
+        } else if left == Some(0) {
+            GRAY_700.with_alpha(0.4).into()
+        } else {
+            GRAY_700.into()
+        };
+        let size = if kind == quiver.selected() { 20. } else { 16. };
+        commands.spawn((
+            Text::new(line),
+            TextColor(color),
+            TextFont::from_font_size(size),
+            ChildOf(*slots),
+        ));
+    }
+}
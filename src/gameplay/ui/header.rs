@@ -1,19 +1,26 @@
 use std::time::Duration;
 
-use bevy::color::palettes::tailwind::GRAY_700;
+use bevy::color::palettes::tailwind::{GRAY_700, RED_700};
 
 use crate::gameplay::{
-    level::Level,
-    sphere::{MarkedForDeletion, MustMark, Sphere},
+    level::{Level, Levels},
+    sphere::{Hazard, MarkedForDeletion, MustMark, Portal, RuleBroken, Sphere},
 };
 
 use super::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<BallCountState>().add_systems(
-        Update,
-        (update_level_info, (tick_bctimer, update_ball_count).chain()),
-    );
+    app.init_resource::<BallCountState>()
+        .init_resource::<RuleNoticeTimer>()
+        .add_observer(show_broken_rule)
+        .add_systems(
+            Update,
+            (
+                update_level_info,
+                (tick_bctimer, update_ball_count).chain(),
+                hide_rule_notice,
+            ),
+        );
 }
 
 #[derive(Resource)]
@@ -37,8 +44,16 @@ fn tick_bctimer(mut state: ResMut<BallCountState>, time: Res<Time>) {
 }
 
 fn update_ball_count(
-    sensor_balls: Query<(), (With<Sphere>, Without<MustMark>)>,
-    markable_balls: Query<(), (With<MustMark>, Without<MarkedForDeletion>)>,
+    sensor_balls: Query<
+        (),
+        (
+            With<Sphere>,
+            Without<MustMark>,
+            Without<Portal>,
+            Without<Hazard>,
+        ),
+    >,
+    markable_balls: Query<(), (With<MustMark>, Without<MarkedForDeletion>, Without<Hazard>)>,
     mut ball_count: Single<&mut Text, With<BallCountText>>,
     mut state: ResMut<BallCountState>,
 ) {
@@ -67,6 +82,35 @@ fn update_ball_count(
     }
 }
 
+/// How long a broken rule is shown under the remaining targets
+#[derive(Resource)]
+struct RuleNoticeTimer(Timer);
+impl Default for RuleNoticeTimer {
+    fn default() -> Self {
+        Self(Timer::new(Duration::from_secs(3), TimerMode::Once))
+    }
+}
+
+fn show_broken_rule(
+    trigger: Trigger<RuleBroken>,
+    mut notice: Single<&mut Text, With<RuleNoticeText>>,
+    mut timer: ResMut<RuleNoticeTimer>,
+) {
+    notice.0 = trigger.event().to_string();
+    timer.0.reset();
+}
+
+fn hide_rule_notice(
+    mut notice: Single<&mut Text, With<RuleNoticeText>>,
+    mut timer: ResMut<RuleNoticeTimer>,
+    time: Res<Time>,
+) {
+    timer.0.tick(time.delta());
+    if timer.0.just_finished() {
+        notice.0.clear();
+    }
+}
+
 #[derive(Component)]
 pub struct Header;
 
@@ -107,8 +151,53 @@ pub fn header() -> impl Bundle {
 #[derive(Component)]
 pub struct LevelInfo;
 
-fn update_level_info(level: Res<Level>, mut level_info: Single<&mut Text, With<LevelInfo>>) {
-    level_info.0 = (level.0 + 1).to_string();
+#[derive(Component)]
+pub struct LevelNumber;
+
+/// The author, hint and newly introduced sphere of the level
+#[derive(Component)]
+pub struct LevelDetails;
+
+fn update_level_info(
+    level: Res<Level>,
+    levels: Res<Levels>,
+    mut level_number: Single<&mut Text, (With<LevelNumber>, Without<LevelInfo>)>,
+    mut level_info: Single<&mut Text, (With<LevelInfo>, Without<LevelDetails>)>,
+    mut level_details: Single<&mut Text, (With<LevelDetails>, Without<LevelNumber>)>,
+) {
+    let number = format!("Level {}", *level);
+    let Some(props) = levels.iter().nth(level.0) else {
+        return;
+    };
+
+    let name = if props.name().is_empty() {
+        level.to_string()
+    } else {
+        props.name().to_string()
+    };
+
+    let mut details = Vec::new();
+    if !props.author().is_empty() {
+        details.push(format!("by {}", props.author()));
+    }
+    if let Some(sphere_type) = props.introduces() {
+        details.push(format!("New: {sphere_type}"));
+    }
+    if let Some(hint) = props.hint() {
+        details.push(hint.to_string());
+    }
+    let details = details.join("\n");
+
+    // only touch the text when it changes, so the ui isn't laid out every frame
+    if level_number.0 != number {
+        level_number.0 = number;
+    }
+    if level_info.0 != name {
+        level_info.0 = name;
+    }
+    if level_details.0 != details {
+        level_details.0 = details;
+    }
 }
 
 fn level_info() -> impl Bundle {
@@ -117,15 +206,23 @@ fn level_info() -> impl Bundle {
             flex_direction: FlexDirection::Column,
             justify_content: JustifyContent::SpaceBetween,
             align_items: AlignItems::Center,
+            max_width: Px(320.),
             ..default()
         },
         children![
-            (Text::new("Course"), TextColor(GRAY_700.into())),
+            (LevelNumber, Text::new("Level"), TextColor(GRAY_700.into())),
             (
                 LevelInfo,
                 Text::new("N/A"),
                 TextColor(Color::BLACK),
                 TextFont::from_font_size(30.),
+            ),
+            (
+                LevelDetails,
+                Text::default(),
+                TextColor(GRAY_700.into()),
+                TextFont::from_font_size(16.),
+                TextLayout::new_with_justify(JustifyText::Center),
             )
         ],
     );
@@ -156,6 +253,10 @@ fn level_info() -> impl Bundle {
 #[derive(Component)]
 pub struct BallCountText;
 
+/// The last rule the player broke, like breaking a hazard
+#[derive(Component)]
+pub struct RuleNoticeText;
+
 fn ball_count() -> impl Bundle {
     let ball_count_text = (
         Node {
@@ -171,6 +272,12 @@ fn ball_count() -> impl Bundle {
                 Text::new("5"),
                 TextColor(Color::BLACK),
                 TextFont::from_font_size(40.),
+            ),
+            (
+                RuleNoticeText,
+                Text::default(),
+                TextColor(RED_700.into()),
+                TextFont::from_font_size(16.),
             )
         ],
     );
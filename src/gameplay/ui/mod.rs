@@ -8,8 +8,11 @@ pub use footer::*;
 mod header;
 pub use header::*;
 
+mod touch;
+pub use touch::*;
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((header::plugin, footer::plugin));
+    app.add_plugins((header::plugin, footer::plugin, touch::plugin));
     app.add_systems(OnEnter(Screen::Gameplay), setup);
 }
 
@@ -43,5 +46,6 @@ fn content() -> impl Bundle {
         },
         Pickable::IGNORE,
         Content,
+        children![touch_buttons()],
     )
 }
@@ -0,0 +1,122 @@
+use bevy::{ecs::system::IntoObserverSystem, input_focus::tab_navigation::TabGroup};
+
+use crate::{
+    gameplay::{
+        GameState,
+        arrow::CancelArrow,
+        bow::BowArrow,
+        cursor::PointerSource,
+        level::Level,
+        mulligan::{Mulligan, TakeMulligan},
+    },
+    theme::{interaction::OnPress, widgets},
+};
+
+use super::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            show_touch_buttons,
+            update_mulligan_button
+                .run_if(resource_changed::<Mulligan>.or(resource_changed::<Level>)),
+        ),
+    );
+}
+
+#[derive(Component)]
+pub struct UiTouchButtons;
+
+#[derive(Component)]
+pub struct UiMulliganButton;
+
+/// Big enough buttons to hit with a finger, only shown while playing with touch
+pub fn touch_buttons() -> impl Bundle {
+    (
+        Name::new("Touch Buttons"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(10.),
+            top: Px(10.),
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.),
+            display: Display::None,
+            ..default()
+        },
+        Pickable::IGNORE,
+        UiTouchButtons,
+        TabGroup::new(0),
+        children![
+            touch_button("Pause", pause),
+            (touch_button("Mulligan", mulligan), UiMulliganButton),
+            touch_button("Cancel", cancel),
+        ],
+    )
+}
+
+fn touch_button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    widgets::button_base(
+        text,
+        action,
+        (
+            Node {
+                width: Px(200.),
+                height: Px(80.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BorderRadius::all(Px(12.)),
+        ),
+    )
+}
+
+fn pause(
+    _: Trigger<OnPress>,
+    state: Res<State<GameState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if *state.get() == GameState::Playing {
+        game_state.set(GameState::Paused);
+    }
+}
+
+fn mulligan(_: Trigger<OnPress>, mut commands: Commands) {
+    commands.trigger(TakeMulligan);
+}
+
+fn cancel(_: Trigger<OnPress>, mut commands: Commands, bows: Query<&BowArrow>) {
+    for bow in &bows {
+        commands.trigger_targets(CancelArrow, bow.arrow());
+    }
+}
+
+fn show_touch_buttons(mut ui: Single<&mut Node, With<UiTouchButtons>>, source: Res<PointerSource>) {
+    // checked every frame, since the HUD is rebuilt for every run of a course
+    let display = if *source == PointerSource::Touch {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    if ui.display != display {
+        ui.display = display;
+    }
+}
+
+fn update_mulligan_button(
+    mut ui: Single<&mut Node, With<UiMulliganButton>>,
+    mulligans: Res<Mulligan>,
+    level: Res<Level>,
+) {
+    ui.display = if mulligans.can_mulligan(level.0) {
+        Display::Flex
+    } else {
+        Display::None
+    };
+}
@@ -37,3 +37,8 @@ where
     let mut rng = RNG.lock().unwrap();
     rng.random_range(range)
 }
+
+/// A separate RNG that always gives the same numbers for the same seed
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
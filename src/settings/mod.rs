@@ -22,6 +22,9 @@ pub struct Settings {
     pub sfx: Volume,
     pub music: Volume,
     pub restart: KeyCode,
+    pub pad_restart: GamepadButton,
+    /// traces the shot while the bow is drawn, on practice courses and in the editor
+    pub aim_preview: bool,
 
     #[cfg(feature = "dev")]
     pub debug_toggle: KeyCode,
@@ -36,6 +39,8 @@ impl Settings {
             sfx: Volume::Linear(1.),
             music: Volume::Linear(0.),
             restart: KeyCode::KeyU,
+            pad_restart: GamepadButton::West,
+            aim_preview: false,
             #[cfg(feature = "dev")]
             debug_toggle: KeyCode::KeyY,
             #[cfg(feature = "dev")]
@@ -50,6 +55,8 @@ impl Default for Settings {
             sfx: Volume::Linear(1.),
             music: Volume::Linear(1.),
             restart: KeyCode::KeyR,
+            pad_restart: GamepadButton::West,
+            aim_preview: false,
             #[cfg(feature = "dev")]
             debug_toggle: KeyCode::KeyF,
             #[cfg(feature = "dev")]
@@ -6,7 +6,7 @@ use bevy::{
 
 use crate::{
     settings::{Settings, SettingsState},
-    theme::widgets,
+    theme::{interaction::OnPress, widgets},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -18,7 +18,12 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        (update_music_volume_label, update_sfx_volume_label).run_if(in_state(SettingsState::View)),
+        (
+            update_music_volume_label,
+            update_sfx_volume_label,
+            update_aim_preview_label,
+        )
+            .run_if(in_state(SettingsState::View)),
     );
 }
 
@@ -32,7 +37,7 @@ fn spawn_settings_menu(mut commands: Commands) {
             settings_grid(),
             widgets::button(
                 "Back",
-                |_: Trigger<Pointer<Click>>, mut settings: ResMut<NextState<SettingsState>>| {
+                |_: Trigger<OnPress>, mut settings: ResMut<NextState<SettingsState>>| {
                     settings.set(SettingsState::None);
                 }
             )
@@ -70,6 +75,14 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             sfx_volume_widget(),
+            (
+                widgets::label("Aim Preview (Practice)"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            aim_preview_widget(),
         ],
     )
 }
@@ -99,12 +112,12 @@ fn music_volume_widget() -> impl Bundle {
     )
 }
 
-fn lower_music_volume(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+fn lower_music_volume(_: Trigger<OnPress>, mut settings: ResMut<Settings>) {
     let linear = (settings.music.to_linear() - 0.1).max(MIN_VOLUME);
     settings.music = Volume::Linear(linear);
 }
 
-fn raise_music_volume(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+fn raise_music_volume(_: Trigger<OnPress>, mut settings: ResMut<Settings>) {
     let linear = (settings.music.to_linear() + 0.1).min(MAX_VOLUME);
     settings.music = Volume::Linear(linear);
 }
@@ -144,12 +157,12 @@ fn sfx_volume_widget() -> impl Bundle {
     )
 }
 
-fn lower_sfx_volume(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+fn lower_sfx_volume(_: Trigger<OnPress>, mut settings: ResMut<Settings>) {
     let linear = (settings.sfx.to_linear() - 0.1).max(MIN_VOLUME);
     settings.sfx = Volume::Linear(linear);
 }
 
-fn raise_sfx_volume(_: Trigger<Pointer<Click>>, mut settings: ResMut<Settings>) {
+fn raise_sfx_volume(_: Trigger<OnPress>, mut settings: ResMut<Settings>) {
     let linear = (settings.sfx.to_linear() + 0.1).min(MAX_VOLUME);
     settings.sfx = Volume::Linear(linear);
 }
@@ -165,3 +178,40 @@ fn update_sfx_volume_label(
     let percent = 100.0 * global_volume.sfx.to_linear();
     label.0 = format!("{percent:3.0}%");
 }
+
+fn aim_preview_widget() -> impl Bundle {
+    (
+        Name::new("Aim Preview Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            (
+                Name::new("Aim Preview"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widgets::label(""), AimPreviewLabel)],
+            ),
+            widgets::button_small("~", toggle_aim_preview),
+        ],
+    )
+}
+
+fn toggle_aim_preview(_: Trigger<OnPress>, mut settings: ResMut<Settings>) {
+    settings.aim_preview = !settings.aim_preview;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AimPreviewLabel;
+
+fn update_aim_preview_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<AimPreviewLabel>>,
+) {
+    label.0 = if settings.aim_preview { "On" } else { "Off" }.to_string();
+}
@@ -0,0 +1,95 @@
+//! Moves focus between menu buttons with a gamepad.
+//!
+//! The d-pad steps through the buttons of the open menus in order and the south button (A on an
+//! Xbox pad) presses the focused one. Tab and shift-tab step through them on a keyboard.
+
+use bevy::{
+    input_focus::{
+        InputDispatchPlugin, InputFocus, InputFocusVisible,
+        tab_navigation::{NavAction, TabNavigation, TabNavigationPlugin},
+    },
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::theme::{interaction::OnPress, palette::BUTTON_FOCUSED_OUTLINE};
+
+const NEXT: [GamepadButton; 2] = [GamepadButton::DPadDown, GamepadButton::DPadRight];
+const PREVIOUS: [GamepadButton; 2] = [GamepadButton::DPadUp, GamepadButton::DPadLeft];
+const PRESS: GamepadButton = GamepadButton::South;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((InputDispatchPlugin, TabNavigationPlugin))
+        .add_systems(
+            Update,
+            (
+                navigate_with_gamepad,
+                press_focused,
+                show_focus.run_if(
+                    resource_changed::<InputFocus>.or(resource_changed::<InputFocusVisible>),
+                ),
+            )
+                .chain(),
+        );
+}
+
+fn navigate_with_gamepad(
+    gamepads: Query<&Gamepad>,
+    navigation: TabNavigation,
+    mut focus: ResMut<InputFocus>,
+    mut visible: ResMut<InputFocusVisible>,
+) {
+    let action = gamepads.iter().find_map(|pad| {
+        if NEXT.iter().any(|button| pad.just_pressed(*button)) {
+            Some(NavAction::Next)
+        } else if PREVIOUS.iter().any(|button| pad.just_pressed(*button)) {
+            Some(NavAction::Previous)
+        } else {
+            None
+        }
+    });
+    let Some(action) = action else {
+        return;
+    };
+    // the focused button may have gone away with its menu, so start over from the first one
+    let next = navigation
+        .navigate(&focus, action)
+        .or_else(|_| navigation.navigate(&InputFocus::default(), NavAction::First));
+    if let Ok(next) = next {
+        focus.set(next);
+        visible.0 = true;
+    }
+}
+
+fn press_focused(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    focus: Res<InputFocus>,
+    buttons: Query<(), With<Button>>,
+) {
+    if !gamepads.iter().any(|pad| pad.just_pressed(PRESS)) {
+        return;
+    }
+    let Some(focused) = focus.get().filter(|entity| buttons.contains(*entity)) else {
+        return;
+    };
+    commands.trigger_targets(OnPress, focused);
+}
+
+fn show_focus(
+    mut commands: Commands,
+    focus: Res<InputFocus>,
+    visible: Res<InputFocusVisible>,
+    buttons: Query<(Entity, Has<Outline>), With<Button>>,
+) {
+    for (button, outlined) in &buttons {
+        let focused = visible.0 && focus.get() == Some(button);
+        if focused && !outlined {
+            commands
+                .entity(button)
+                .insert(Outline::new(Px(4.), Px(2.), BUTTON_FOCUSED_OUTLINE));
+        } else if !focused && outlined {
+            commands.entity(button).remove::<Outline>();
+        }
+    }
+}
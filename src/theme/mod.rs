@@ -1,8 +1,9 @@
+mod focus;
 pub mod interaction;
 pub mod palette;
 pub mod widgets;
 
 use bevy::prelude::*;
 pub fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, focus::plugin));
 }
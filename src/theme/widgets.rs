@@ -4,13 +4,15 @@ use std::borrow::Cow;
 
 use bevy::{
     ecs::{spawn::SpawnWith, system::IntoObserverSystem},
+    input_focus::tab_navigation::{TabGroup, TabIndex},
     prelude::*,
     ui::Val::*,
 };
 
 use crate::theme::{interaction::InteractionPalette, palette::*};
 
-/// A root UI node that fills the window and centers its content.
+/// A root UI node that fills the window and centers its content. Its buttons can be focused with
+/// a gamepad.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
     (
         Name::new(name),
@@ -26,6 +28,7 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
         },
         // Don't block picking events for other UI roots.
         Pickable::IGNORE,
+        TabGroup::new(0),
     )
 }
 
@@ -114,6 +117,7 @@ where
                 .spawn((
                     Name::new("Button Inner"),
                     Button,
+                    TabIndex(0),
                     BackgroundColor(BUTTON_BACKGROUND),
                     InteractionPalette {
                         none: BUTTON_BACKGROUND,
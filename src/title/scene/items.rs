@@ -9,10 +9,8 @@ use crate::{
     gameplay::{
         arrow::ArrowAssets,
         bow::BowAssets,
-        sphere::{
-            Absorber, Bouncy, Exploder, GravitySphere, Multiplier, Normal, Sphere, SphereAssets,
-            TimeFreeze,
-        },
+        level::SphereType,
+        sphere::{Sphere, SphereAssets},
     },
     world::{BACKDROP_OFFSET, BLOCK_LEN},
 };
@@ -145,48 +143,43 @@ fn spawn_items(
         Sphere,
         mesh.clone(),
         Prop,
-        Normal,
-        MeshMaterial3d(sphere.normal.clone()),
+        SphereType::NORMAL,
     ));
     commands.spawn((
         Name::new("Title Screen Multiplier Sphere"),
         SphereCount(1),
         Sphere,
         Transform::from_xyz(0., 0., -200.),
-        Multiplier,
+        SphereType::MULTIPLIER,
         Prop,
         mesh.clone(),
-        MeshMaterial3d(sphere.multiplier.clone()),
     ));
     commands.spawn((
         Name::new("Title Screen TimeFreeze Sphere"),
         SphereCount(2),
         Transform::from_xyz(0., 0., -300.),
         Sphere,
-        TimeFreeze,
+        SphereType::TIME_FREEZE,
         Prop,
         mesh.clone(),
-        MeshMaterial3d(sphere.time_freeze.clone()),
     ));
     commands.spawn((
         Name::new("Title Screen Absorber Sphere"),
         SphereCount(3),
         Transform::from_xyz(0., 0., -400.),
         Sphere,
-        Absorber,
+        SphereType::ABSORBER,
         Prop,
         mesh.clone(),
-        MeshMaterial3d(sphere.absorber.clone()),
     ));
     commands.spawn((
         Name::new("Title Screen Gravity Sphere"),
         SphereCount(4),
         Transform::from_xyz(0., 0., -500.),
         Sphere,
-        GravitySphere,
+        SphereType::GRAVITY,
         Prop,
         mesh.clone(),
-        MeshMaterial3d(sphere.gravity.clone()),
         OutlineVolume {
             visible: true,
             colour: Color::srgb(0.0, 0.0, 0.0),
@@ -198,10 +191,9 @@ fn spawn_items(
         SphereCount(5),
         Transform::from_xyz(0., 0., -600.),
         Sphere,
-        Bouncy,
+        SphereType::BOUNCY,
         Prop,
         mesh.clone(),
-        MeshMaterial3d(sphere.bouncy.clone()),
     ));
     commands.spawn((
         Name::new("Title Screen Exploder Sphere"),
@@ -209,9 +201,8 @@ fn spawn_items(
         Sphere,
         Transform::from_xyz(0., 0., -700.),
         Prop,
-        Exploder,
+        SphereType::EXPLODER,
         mesh.clone(),
-        MeshMaterial3d(sphere.exploder.clone()),
     ));
 }
 
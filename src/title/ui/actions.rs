@@ -1,10 +1,13 @@
+use super::courses::CourseSelectState;
 use crate::{
-    Screen,
     credits::CreditsState,
     settings::SettingsState,
     theme::{interaction::OnPress, widgets},
 };
-use bevy::prelude::{Val::*, *};
+use bevy::{
+    input_focus::tab_navigation::TabGroup,
+    prelude::{Val::*, *},
+};
 
 #[derive(Component)]
 struct Actions;
@@ -22,10 +25,11 @@ pub fn spawn_actions() -> impl Bundle {
             ..default()
         },
         Actions,
+        TabGroup::new(0),
         #[cfg(target_family = "wasm")]
         {
             children![
-                widgets::button("Play", transition_to_gameplay),
+                widgets::button("Play", enter_course_select),
                 widgets::button("Credits", enter_credits_screen),
                 widgets::button("Settings", enter_settings),
             ]
@@ -33,7 +37,8 @@ pub fn spawn_actions() -> impl Bundle {
         #[cfg(not(target_family = "wasm"))]
         {
             children![
-                widgets::button("Play", transition_to_gameplay),
+                widgets::button("Play", enter_course_select),
+                widgets::button("Editor", open_editor),
                 widgets::button("Credits", enter_credits_screen),
                 widgets::button("Settings", enter_settings),
                 widgets::button("Exit", exit_app),
@@ -42,8 +47,21 @@ pub fn spawn_actions() -> impl Bundle {
     )
 }
 
-fn transition_to_gameplay(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Transition);
+fn enter_course_select(
+    _trigger: Trigger<OnPress>,
+    mut next_screen: ResMut<NextState<CourseSelectState>>,
+) {
+    next_screen.set(CourseSelectState::View);
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn open_editor(
+    _trigger: Trigger<OnPress>,
+    mut commands: Commands,
+    course: Res<crate::gameplay::level::CurrentCourse>,
+) {
+    commands.init_resource::<crate::gameplay::level::EditorSession>();
+    commands.trigger(crate::gameplay::level::PlayCourse(course.0));
 }
 
 fn enter_credits_screen(
@@ -0,0 +1,67 @@
+//! Picks the course to play before going into gameplay.
+
+use bevy::{
+    color::palettes::tailwind::SKY_900, ecs::spawn::SpawnIter,
+    input::common_conditions::input_just_pressed, prelude::*,
+};
+
+use crate::{
+    Screen,
+    gameplay::level::{Courses, PlayCourse},
+    theme::{interaction::OnPress, widgets},
+};
+
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default, Reflect)]
+#[source(Screen = Screen::Title)]
+#[states(scoped_entities)]
+pub enum CourseSelectState {
+    #[default]
+    None,
+    View,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<CourseSelectState>()
+        .add_systems(OnEnter(CourseSelectState::View), spawn_course_select)
+        .add_systems(
+            Update,
+            go_back
+                .run_if(in_state(CourseSelectState::View).and(input_just_pressed(KeyCode::Escape))),
+        );
+}
+
+fn spawn_course_select(mut commands: Commands, courses: Res<Courses>) {
+    let buttons: Vec<_> = courses
+        .iter()
+        .enumerate()
+        .map(|(index, course)| {
+            widgets::button(
+                format!("{} {}", course.name(), course.num_levels()),
+                move |_: Trigger<OnPress>, mut commands: Commands| {
+                    commands.trigger(PlayCourse(index));
+                },
+            )
+        })
+        .collect();
+
+    commands.spawn((
+        widgets::ui_root("Course Select"),
+        BackgroundColor(SKY_900.into()),
+        StateScoped(CourseSelectState::View),
+        GlobalZIndex(4),
+        Children::spawn((
+            Spawn(widgets::header("Courses")),
+            SpawnIter(buttons.into_iter()),
+            Spawn(widgets::button(
+                "Back",
+                |_: Trigger<OnPress>, mut next_state: ResMut<NextState<CourseSelectState>>| {
+                    next_state.set(CourseSelectState::None);
+                },
+            )),
+        )),
+    ));
+}
+
+fn go_back(mut next_state: ResMut<NextState<CourseSelectState>>) {
+    next_state.set(CourseSelectState::None);
+}
@@ -10,8 +10,10 @@ use bevy::{
 
 mod actions;
 use actions::spawn_actions;
+mod courses;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(courses::plugin);
     app.register_type::<UiAssets>()
         .load_resource::<UiAssets>()
         .add_systems(OnEnter(Screen::Title), spawn_title_screen);